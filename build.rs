@@ -0,0 +1,21 @@
+use std::process::Command;
+
+// 编译时记录 git commit 和构建时间，供健康检查输出
+fn main() {
+    let git_commit = std::env::var("GIT_COMMIT").ok().unwrap_or_else(|| {
+        Command::new("git")
+            .args(["rev-parse", "--short", "HEAD"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|commit| commit.trim().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    });
+
+    println!("cargo:rustc-env=GIT_COMMIT={}", git_commit);
+    println!("cargo:rustc-env=BUILD_TIME={}", chrono::Utc::now().to_rfc3339());
+    println!("cargo:rerun-if-env-changed=GIT_COMMIT");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+}
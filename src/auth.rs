@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet};
+use axum::{extract::FromRequestParts, http::request::Parts};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::{Deserialize, Serialize};
+use crate::config::{ApiKeySettings, AuthSettings, JwtSettings};
+use crate::error::AppError;
+
+// 权限标识，在配置 [auth.roles] 中分配给角色
+pub mod permissions {
+    pub const ACCOUNTS_READ: &str = "accounts:read";
+    pub const ACCOUNTS_WRITE: &str = "accounts:write";
+    pub const ACCOUNTS_CREDENTIALS: &str = "accounts:credentials";
+    // 调整账号负责人
+    pub const ACCOUNTS_ASSIGN: &str = "accounts:assign";
+    // 开启负责人隔离时仍可访问全部账号
+    pub const ACCOUNTS_ALL: &str = "accounts:all";
+    // 查看（include_deleted）和恢复已删除的账号
+    pub const ACCOUNTS_DELETED: &str = "accounts:deleted";
+    // 查询全局审计日志
+    pub const AUDIT_READ: &str = "audit:read";
+    // 拥有全部权限
+    pub const ALL: &str = "*";
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthMethod {
+    ApiKey,
+    Jwt,
+    // 未启用认证时的匿名调用方
+    Anonymous,
+}
+
+// 调用方可访问的账号范围，由 AccountService 在查询时统一应用
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AccountScope {
+    All,
+    // 只能访问 user_name 在列表中的账号
+    Owners(Vec<String>),
+}
+
+// 已认证的调用方，由认证中间件放入请求 extensions
+#[derive(Clone, Debug, Serialize)]
+pub struct Principal {
+    pub subject: String,
+    pub method: AuthMethod,
+    pub roles: Vec<String>,
+    pub permissions: HashSet<String>,
+    pub account_scope: AccountScope,
+}
+
+impl AccountScope {
+    // 调用方是否可以看到/指定该负责人
+    pub fn allows_owner(&self, user_name: &str) -> bool {
+        match self {
+            AccountScope::All => true,
+            AccountScope::Owners(owners) => owners.iter().any(|owner| owner == user_name),
+        }
+    }
+}
+
+impl Principal {
+    // 认证关闭时使用，拥有全部权限
+    pub fn anonymous() -> Self {
+        Self {
+            subject: "anonymous".to_string(),
+            method: AuthMethod::Anonymous,
+            roles: Vec::new(),
+            permissions: HashSet::from([permissions::ALL.to_string()]),
+            account_scope: AccountScope::All,
+        }
+    }
+
+    pub fn has_permission(&self, permission: &str) -> bool {
+        self.permissions.contains(permission) || self.permissions.contains(permissions::ALL)
+    }
+}
+
+impl<S> FromRequestParts<S> for Principal
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<Principal>()
+            .cloned()
+            .ok_or_else(|| AppError::Unauthorized("authentication required".to_string()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Claims {
+    sub: String,
+    #[serde(default)]
+    roles: Vec<String>,
+    // 对应账号表的 user_name，缺省时使用 sub
+    user_name: Option<String>,
+    #[serde(default)]
+    teams: Vec<String>,
+}
+
+// 认证成功后解析出的身份信息，用于构建 Principal
+struct Identity {
+    subject: String,
+    method: AuthMethod,
+    user_name: Option<String>,
+    roles: Vec<String>,
+    teams: Vec<String>,
+}
+
+struct JwtVerifier {
+    key: DecodingKey,
+    validation: Validation,
+}
+
+// 根据配置校验 API Key 和 JWT Bearer Token
+pub struct Authenticator {
+    enabled: bool,
+    public_paths: Vec<String>,
+    api_keys: Vec<ApiKeySettings>,
+    jwt: Option<JwtVerifier>,
+    // 角色 -> 权限列表
+    roles: HashMap<String, Vec<String>>,
+    owner_scoping: bool,
+    // 团队 -> 成员 user_name 列表
+    teams: HashMap<String, Vec<String>>,
+}
+
+impl Authenticator {
+    pub fn from_settings(settings: &AuthSettings) -> Result<Self, Box<dyn std::error::Error>> {
+        let api_keys = settings
+            .api_keys
+            .iter()
+            .filter(|api_key| !api_key.key.is_empty())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let jwt = settings.jwt.as_ref().map(build_jwt_verifier).transpose()?;
+
+        if settings.enabled && api_keys.is_empty() && jwt.is_none() {
+            return Err("auth is enabled but neither api_keys nor jwt is configured".into());
+        }
+
+        Ok(Self {
+            enabled: settings.enabled,
+            public_paths: settings.public_paths.clone(),
+            api_keys,
+            jwt,
+            roles: settings.roles.clone(),
+            owner_scoping: settings.owner_scoping,
+            teams: settings.teams.clone(),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    // 支持精确匹配和以 "/*" 结尾的前缀匹配
+    pub fn is_public_path(&self, path: &str) -> bool {
+        self.public_paths.iter().any(|pattern| match pattern.strip_suffix("/*") {
+            Some(prefix) => path == prefix || path.starts_with(&format!("{}/", prefix)),
+            None => path == pattern,
+        })
+    }
+
+    pub fn authenticate_api_key(&self, key: &str) -> Result<Principal, AppError> {
+        self.api_keys
+            .iter()
+            .find(|candidate| constant_time_eq(candidate.key.as_bytes(), key.as_bytes()))
+            .map(|api_key| {
+                self.build_principal(Identity {
+                    subject: api_key.subject.clone(),
+                    method: AuthMethod::ApiKey,
+                    user_name: api_key.user_name.clone(),
+                    roles: api_key.roles.clone(),
+                    teams: api_key.teams.clone(),
+                })
+            })
+            .ok_or_else(|| AppError::Unauthorized("invalid api key".to_string()))
+    }
+
+    pub fn authenticate_bearer(&self, token: &str) -> Result<Principal, AppError> {
+        let verifier = self
+            .jwt
+            .as_ref()
+            .ok_or_else(|| AppError::Unauthorized("bearer tokens are not accepted".to_string()))?;
+
+        let data = jsonwebtoken::decode::<Claims>(token, &verifier.key, &verifier.validation)
+            .map_err(|e| AppError::Unauthorized(format!("invalid bearer token: {}", e)))?;
+
+        let claims = data.claims;
+        Ok(self.build_principal(Identity {
+            subject: claims.sub,
+            method: AuthMethod::Jwt,
+            user_name: claims.user_name,
+            roles: claims.roles,
+            teams: claims.teams,
+        }))
+    }
+
+    // 按角色策略展开权限，未在配置中定义的角色不授予任何权限
+    fn build_principal(&self, identity: Identity) -> Principal {
+        let granted: HashSet<String> = identity
+            .roles
+            .iter()
+            .filter_map(|role| self.roles.get(role))
+            .flatten()
+            .cloned()
+            .collect();
+
+        let sees_all = granted.contains(permissions::ACCOUNTS_ALL)
+            || granted.contains(permissions::ALL);
+
+        // 负责人隔离：本人 + 所在团队成员负责的账号
+        let account_scope = if !self.owner_scoping || sees_all {
+            AccountScope::All
+        } else {
+            let mut owners = vec![identity.user_name.unwrap_or_else(|| identity.subject.clone())];
+            for team in &identity.teams {
+                owners.extend(self.teams.get(team).into_iter().flatten().cloned());
+            }
+            owners.sort();
+            owners.dedup();
+            AccountScope::Owners(owners)
+        };
+
+        Principal {
+            subject: identity.subject,
+            method: identity.method,
+            roles: identity.roles,
+            permissions: granted,
+            account_scope,
+        }
+    }
+}
+
+fn build_jwt_verifier(settings: &JwtSettings) -> Result<JwtVerifier, Box<dyn std::error::Error>> {
+    let (algorithm, key) = match settings.algorithm.to_uppercase().as_str() {
+        "HS256" => {
+            let secret = settings.secret.as_deref().unwrap_or_default();
+            if secret.is_empty() {
+                return Err("auth.jwt.secret is required for HS256".into());
+            }
+            (Algorithm::HS256, DecodingKey::from_secret(secret.as_bytes()))
+        }
+        "RS256" => {
+            let path = settings
+                .public_key_path
+                .as_deref()
+                .ok_or("auth.jwt.public_key_path is required for RS256")?;
+            let pem = std::fs::read(path)?;
+            (Algorithm::RS256, DecodingKey::from_rsa_pem(&pem)?)
+        }
+        other => return Err(format!("unsupported jwt algorithm: {}", other).into()),
+    };
+
+    let mut validation = Validation::new(algorithm);
+    validation.leeway = settings.leeway;
+    if let Some(issuer) = &settings.issuer {
+        validation.set_issuer(&[issuer]);
+    }
+    match &settings.audience {
+        Some(audience) => validation.set_audience(&[audience]),
+        None => validation.validate_aud = false,
+    }
+
+    Ok(JwtVerifier { key, validation })
+}
+
+// 避免通过比较耗时推测 API Key
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,108 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use moka::future::Cache;
+use crate::config::CacheSettings;
+use crate::etag::CollectionVersion;
+use crate::metrics::Metrics;
+use crate::models::account::{AccountSummaryResponse, AccountsListResponse};
+
+// 账号读缓存：列表分页、统计和集合版本，任一账号写入后整体失效。
+// 缓存只在本进程内有效，其他实例或 import 子命令的写入要等 ttl 过期后才可见
+#[derive(Clone)]
+pub struct AccountCache {
+    enabled: bool,
+    // 每次失效加一并拼进缓存键。moka 的 invalidate_all 不会取消进行中的加载，
+    // 失效前开始的加载完成后只会写入旧代的键，不会被之后的读取命中
+    generation: Arc<AtomicU64>,
+    lists: Cache<String, Arc<AccountsListResponse>>,
+    summaries: Cache<String, Arc<AccountSummaryResponse>>,
+    versions: Cache<String, CollectionVersion>,
+    metrics: Arc<Metrics>,
+}
+
+impl AccountCache {
+    pub fn new(settings: &CacheSettings, metrics: Arc<Metrics>) -> Self {
+        let ttl = Duration::from_secs(settings.ttl);
+
+        Self {
+            enabled: settings.enabled,
+            generation: Arc::new(AtomicU64::new(0)),
+            lists: Cache::builder()
+                .max_capacity(settings.max_capacity)
+                .time_to_live(ttl)
+                .build(),
+            summaries: Cache::builder()
+                .max_capacity(settings.max_capacity)
+                .time_to_live(ttl)
+                .build(),
+            versions: Cache::builder()
+                .max_capacity(settings.max_capacity)
+                .time_to_live(ttl)
+                .build(),
+            metrics,
+        }
+    }
+
+    pub async fn list<F>(&self, key: String, load: F) -> Result<AccountsListResponse, sea_orm::DbErr>
+    where
+        F: Future<Output = Result<AccountsListResponse, sea_orm::DbErr>>,
+    {
+        let value = self.get_or_load(&self.lists, "lists", key, async { load.await.map(Arc::new) }).await?;
+        Ok((*value).clone())
+    }
+
+    pub async fn summary<F>(&self, key: String, load: F) -> Result<AccountSummaryResponse, sea_orm::DbErr>
+    where
+        F: Future<Output = Result<AccountSummaryResponse, sea_orm::DbErr>>,
+    {
+        let value = self.get_or_load(&self.summaries, "summaries", key, async { load.await.map(Arc::new) }).await?;
+        Ok((*value).clone())
+    }
+
+    pub async fn version<F>(&self, key: String, load: F) -> Result<CollectionVersion, sea_orm::DbErr>
+    where
+        F: Future<Output = Result<CollectionVersion, sea_orm::DbErr>>,
+    {
+        self.get_or_load(&self.versions, "versions", key, load).await
+    }
+
+    // 账号写入后调用；缓存键包含调用方可见范围，无法精确定位，直接全部失效
+    pub fn invalidate_all(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.lists.invalidate_all();
+        self.summaries.invalidate_all();
+        self.versions.invalidate_all();
+    }
+
+    // 并发未命中时只有一个请求执行查询，其余等待同一结果
+    async fn get_or_load<V, F>(
+        &self,
+        cache: &Cache<String, V>,
+        name: &str,
+        key: String,
+        load: F,
+    ) -> Result<V, sea_orm::DbErr>
+    where
+        V: Clone + Send + Sync + 'static,
+        F: Future<Output = Result<V, sea_orm::DbErr>>,
+    {
+        if !self.enabled {
+            return load.await;
+        }
+
+        let key = format!("{}|{}", self.generation.load(Ordering::SeqCst), key);
+        let mut loaded = false;
+        let result = cache
+            .try_get_with(key, async {
+                loaded = true;
+                load.await
+            })
+            .await
+            .map_err(|e| sea_orm::DbErr::Custom(e.to_string()));
+
+        self.metrics.observe_cache(name, !loaded);
+        result
+    }
+}
@@ -1,6 +1,7 @@
 use config::{Config, Environment, File};
 use serde::{Deserialize, Serialize};
 use sea_orm::{ConnectOptions, DatabaseConnection};
+use std::collections::HashMap;
 use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,6 +11,14 @@ pub struct AppConfig {
     pub logging: LoggingSettings,
     pub database: DatabaseSettings,
     pub middleware: MiddlewareSettings,
+    pub auth: AuthSettings,
+    pub http_cache: HttpCacheSettings,
+    pub cache: CacheSettings,
+    pub ozon: OzonSettings,
+    pub credential_check: CredentialCheckSettings,
+    pub credential_rotation: CredentialRotationSettings,
+    pub sync: SyncSettings,
+    pub account_retention: AccountRetentionSettings,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +33,30 @@ pub struct AppSettings {
 pub struct ServerSettings {
     pub host: String,
     pub port: u16,
+    // 优雅关闭时等待请求和后台任务结束的最长时间（秒）
+    pub shutdown_timeout: u64,
+    // 未配置时使用明文 HTTP
+    pub tls: Option<TlsSettings>,
+    // 未配置时健康检查挂在公共端口上，管理端点不开放
+    pub admin: Option<AdminSettings>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminSettings {
+    pub host: String,
+    pub port: u16,
+    // 配置后改为监听 Unix domain socket，忽略 host/port
+    pub socket_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsSettings {
+    pub cert_path: String,
+    pub key_path: String,
+    // 配置后要求客户端证书（双向 TLS）
+    pub client_ca_path: Option<String>,
+    // 证书文件变更检查间隔（秒），0 表示不自动重载
+    pub reload_interval: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,16 +90,160 @@ pub struct DatabaseSettings {
     pub enable_logging: bool,
     pub slow_query_log: bool,
     pub slow_query_threshold: u64,
+
+    // 就绪探针 ping 超时（毫秒）
+    pub health_check_timeout: u64,
+
+    // 启动时自动执行数据库迁移
+    pub auto_migrate: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MiddlewareSettings {
+    pub request_id: bool,
     pub trace: bool,
     pub cors: bool,
     pub compression: bool,
     pub catch_panic: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthSettings {
+    pub enabled: bool,
+    // 无需认证的路径，支持 "/prefix/*" 前缀匹配
+    pub public_paths: Vec<String>,
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeySettings>,
+    pub jwt: Option<JwtSettings>,
+    // 角色策略：角色名 -> 权限列表，例如 admin = ["accounts:read", "accounts:write"]
+    #[serde(default)]
+    pub roles: HashMap<String, Vec<String>>,
+    // 开启后，没有 accounts:all 权限的调用方只能访问自己（或团队）负责的账号
+    #[serde(default)]
+    pub owner_scoping: bool,
+    // 团队 -> 成员 user_name 列表
+    #[serde(default)]
+    pub teams: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeySettings {
+    pub key: String,
+    // 认证成功后的调用方标识
+    pub subject: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    // 对应账号表的 user_name，缺省时使用 subject
+    pub user_name: Option<String>,
+    #[serde(default)]
+    pub teams: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JwtSettings {
+    // HS256 或 RS256
+    pub algorithm: String,
+    // HS256 共享密钥
+    pub secret: Option<String>,
+    // RS256 公钥 (PEM)
+    pub public_key_path: Option<String>,
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+    // 过期时间容差（秒）
+    #[serde(default)]
+    pub leeway: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpCacheSettings {
+    // 未单独配置的路由使用的 Cache-Control
+    pub default_cache_control: String,
+    // 路由模板 -> Cache-Control，例如 "/accounts/summary" = "private, max-age=30"
+    #[serde(default)]
+    pub cache_control: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheSettings {
+    pub enabled: bool,
+    // 缓存存活时间（秒）。缓存在每个进程内独立，这也是其他实例、import 子命令
+    // 或外部直接修改数据库后，本实例读到新数据的最长延迟
+    pub ttl: u64,
+    // 每类缓存最多保留的条目数
+    pub max_capacity: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OzonSettings {
+    // Seller API 地址，测试时可指向本地 mock 服务
+    pub base_url: String,
+    // 单次请求超时（秒）
+    pub timeout: u64,
+    // 接口触发的凭证校验的超时（秒），校验请求不重试
+    pub verify_timeout: u64,
+    // 网络错误、429 和 5xx 的最大重试次数
+    pub max_retries: u32,
+    // 首次重试前等待（毫秒），之后按指数增长
+    pub retry_backoff: u64,
+    // 重试等待上限（毫秒）
+    pub max_retry_backoff: u64,
+    // 每个 client_id 每秒最多发出的请求数，0 表示不在本地限流
+    pub rate_limit: f64,
+    // 令牌桶容量，允许的瞬时突发请求数
+    pub burst: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialCheckSettings {
+    // 是否定期校验启用账号的 Ozon 凭证
+    pub enabled: bool,
+    // 校验间隔（秒）
+    pub interval: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CredentialRotationSettings {
+    // 启用新 key 后保留旧 key 的时间（秒），期间可以回滚
+    pub grace_period: u64,
+    // GET /accounts/expiring 默认查询的天数
+    pub expiring_within_days: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncSettings {
+    // 是否定期把启用账号的 Ozon 数据同步到本地表
+    pub enabled: bool,
+    // 同步间隔（秒）
+    pub interval: u64,
+    // 商品/货件列表每页条数（Ozon 上限 1000）
+    pub page_size: u32,
+    // 同步游标保持 running 的最长时间（秒），超时视为上次同步异常中断
+    pub lease: u64,
+    // 首次同步货件时回溯的天数
+    pub posting_lookback_days: u32,
+    // 每次增量同步从上次水位往前重叠的秒数，用于拿到近期货件的状态变化
+    pub posting_overlap: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountRetentionSettings {
+    // 是否定期彻底删除过了保留期的已删除账号；默认关闭，需要显式开启
+    pub enabled: bool,
+    // 删除后保留（可恢复）的天数
+    pub retention_days: u32,
+    // 清理间隔（秒）
+    pub interval: u64,
+}
+
+impl HttpCacheSettings {
+    pub fn cache_control_for(&self, route: &str) -> &str {
+        self.cache_control
+            .get(route)
+            .map(String::as_str)
+            .unwrap_or(&self.default_cache_control)
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -78,6 +255,9 @@ impl Default for AppConfig {
             server: ServerSettings {
                 host: "0.0.0.0".to_string(),
                 port: 3000,
+                shutdown_timeout: 30,
+                tls: None,
+                admin: None,
             },
             logging: LoggingSettings {
                 level: "debug".to_string(),
@@ -102,13 +282,65 @@ impl Default for AppConfig {
                 enable_logging: true,
                 slow_query_log: false,
                 slow_query_threshold: 1000,
+                health_check_timeout: 2000,
+                auto_migrate: true,
             },
             middleware: MiddlewareSettings {
+                request_id: true,
                 trace: true,
                 cors: true,
                 compression: true,
                 catch_panic: true,
             },
+            auth: AuthSettings {
+                enabled: false,
+                public_paths: vec!["/".to_string(), "/health".to_string(), "/health/*".to_string()],
+                api_keys: Vec::new(),
+                jwt: None,
+                roles: HashMap::new(),
+                owner_scoping: false,
+                teams: HashMap::new(),
+            },
+            http_cache: HttpCacheSettings {
+                default_cache_control: "private, no-cache".to_string(),
+                cache_control: HashMap::new(),
+            },
+            cache: CacheSettings {
+                enabled: true,
+                ttl: 30,
+                max_capacity: 1000,
+            },
+            ozon: OzonSettings {
+                base_url: "https://api-seller.ozon.ru".to_string(),
+                timeout: 30,
+                verify_timeout: 5,
+                max_retries: 3,
+                retry_backoff: 500,
+                max_retry_backoff: 10000,
+                rate_limit: 10.0,
+                burst: 20,
+            },
+            credential_check: CredentialCheckSettings {
+                enabled: false,
+                interval: 3600,
+            },
+            credential_rotation: CredentialRotationSettings {
+                grace_period: 86400,
+                expiring_within_days: 14,
+            },
+            sync: SyncSettings {
+                enabled: false,
+                interval: 1800,
+                page_size: 500,
+                lease: 3600,
+                posting_lookback_days: 30,
+                posting_overlap: 259200,
+            },
+            account_retention: AccountRetentionSettings {
+                enabled: false,
+                retention_days: 90,
+                interval: 86400,
+            },
         }
     }
 }
@@ -140,10 +372,17 @@ impl AppConfig {
             &self.logging.level
         };
 
-        format!("axum_learn={},tower_http={},config={}", 
+        let mut filter = format!("axum_learn={},tower_http={},config={}", 
                 base_level, 
                 self.logging.level,
-                base_level)
+                base_level);
+
+        // 慢查询日志由 sqlx 以 warn 级别输出
+        if self.database.slow_query_log {
+            filter.push_str(",sqlx=warn");
+        }
+
+        filter
     }
 
     pub fn get_server_address(&self) -> String {
@@ -1,56 +1,744 @@
-use axum::{extract::{State, Query}, http::StatusCode, Json};
+use std::future::Future;
+use futures::TryStreamExt;
+use std::sync::Arc;
+use axum::{
+    body::{Body, Bytes},
+    extract::{MatchedPath, Path, State, Query},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
 use sea_orm::DatabaseConnection;
-use crate::models::account::{AccountsListResponse, PaginationParams, AccountSummaryResponse};
-use crate::service::account_service::AccountService;
+use serde::Serialize;
+use crate::auth::{permissions, Principal};
+use crate::cache::AccountCache;
+use crate::config::AppConfig;
+use crate::entities::account;
+use crate::error::AppError;
+use crate::export::{encode_accounts, ExportColumn};
+use crate::import::parse_rows;
+use crate::etag::{
+    account_etag, collection_etag, etag_header, expected_version, http_date, is_not_modified,
+};
+use crate::models::account::{
+    AccountCredentialsResponse, AccountListParams, AccountResponse, AccountsListResponse, ApiUsageResponse,
+    BulkRequest, BulkResponse, DuplicateParams, DuplicatesResponse, ExpiringParams, ExportParams,
+    IncludeDeletedParams, PaginationParams, ReassignOwnerRequest, StageKeyRequest, UpdateAccountRequest,
+};
+use crate::models::import::{ImportParams, ImportResponse};
+use crate::models::audit::{AuditListResponse, AuditQueryParams};
+use crate::service::account_service::{AccountService, UpdateResult};
+use crate::service::audit_service::{actions, AuditContext, AuditService};
+use crate::service::rotation_service::RotationService;
+use crate::service::verification_service::VerificationService;
+use crate::ozon::OzonClient;
 
 // 获取所有账号数据（带分页）
 pub async fn list_all_accounts(
     State(db): State<DatabaseConnection>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<AccountsListResponse>, StatusCode> {
-    let page = params.page.unwrap_or(1);
-    let page_size = params.page_size.unwrap_or(20).min(100).max(1);
+    State(cache): State<AccountCache>,
+    State(config): State<Arc<AppConfig>>,
+    principal: Principal,
+    route: MatchedPath,
+    headers: HeaderMap,
+    Query(params): Query<AccountListParams>,
+) -> Result<Response, AppError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(20).clamp(1, 100);
+    let include_deleted = include_deleted(&principal, params.include_deleted)?;
+    let conditional = CacheContext::new(&config, &principal, &route, &headers);
 
-    AccountService::get_all_accounts(&db, page, page_size)
-        .await
-        .map(Json)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    conditional.respond(&db, &cache, None, include_deleted, &format!("{}:{}", page, page_size), || async {
+        let mut response =
+            AccountService::get_all_accounts(&db, &cache, &principal.account_scope, include_deleted, page, page_size).await?;
+        mask_unless_permitted(&principal, &mut response);
+        Ok(response)
+    })
+    .await
 }
 
 // 获取启用的账号（带分页）
 pub async fn list_enabled_accounts(
     State(db): State<DatabaseConnection>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<AccountsListResponse>, StatusCode> {
-    let page = params.page.unwrap_or(1);
-    let page_size = params.page_size.unwrap_or(20).min(100).max(1);
+    State(cache): State<AccountCache>,
+    State(config): State<Arc<AppConfig>>,
+    principal: Principal,
+    route: MatchedPath,
+    headers: HeaderMap,
+    Query(params): Query<AccountListParams>,
+) -> Result<Response, AppError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(20).clamp(1, 100);
+    let include_deleted = include_deleted(&principal, params.include_deleted)?;
+    let conditional = CacheContext::new(&config, &principal, &route, &headers);
 
-    AccountService::get_enabled_accounts(&db, page, page_size)
-        .await
-        .map(Json)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    conditional.respond(&db, &cache, Some(1), include_deleted, &format!("{}:{}", page, page_size), || async {
+        let mut response =
+            AccountService::get_enabled_accounts(&db, &cache, &principal.account_scope, include_deleted, page, page_size).await?;
+        mask_unless_permitted(&principal, &mut response);
+        Ok(response)
+    })
+    .await
 }
 
 // 获取未启用的账号（带分页）
 pub async fn list_disabled_accounts(
     State(db): State<DatabaseConnection>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<AccountsListResponse>, StatusCode> {
-    let page = params.page.unwrap_or(1);
-    let page_size = params.page_size.unwrap_or(20).min(100).max(1);
+    State(cache): State<AccountCache>,
+    State(config): State<Arc<AppConfig>>,
+    principal: Principal,
+    route: MatchedPath,
+    headers: HeaderMap,
+    Query(params): Query<AccountListParams>,
+) -> Result<Response, AppError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(20).clamp(1, 100);
+    let include_deleted = include_deleted(&principal, params.include_deleted)?;
+    let conditional = CacheContext::new(&config, &principal, &route, &headers);
 
-    AccountService::get_disabled_accounts(&db, page, page_size)
-        .await
-        .map(Json)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    conditional.respond(&db, &cache, Some(0), include_deleted, &format!("{}:{}", page, page_size), || async {
+        let mut response =
+            AccountService::get_disabled_accounts(&db, &cache, &principal.account_scope, include_deleted, page, page_size).await?;
+        mask_unless_permitted(&principal, &mut response);
+        Ok(response)
+    })
+    .await
 }
 
 // 获取账号统计信息
 pub async fn get_accounts_summary(
     State(db): State<DatabaseConnection>,
-) -> Result<Json<AccountSummaryResponse>, StatusCode> {
-    AccountService::get_accounts_summary(&db)
-        .await
-        .map(Json)
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+    State(cache): State<AccountCache>,
+    State(config): State<Arc<AppConfig>>,
+    principal: Principal,
+    route: MatchedPath,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let conditional = CacheContext::new(&config, &principal, &route, &headers);
+
+    conditional.respond(&db, &cache, None, false, "", || async {
+        Ok(AccountService::get_accounts_summary(&db, &cache, &principal.account_scope).await?)
+    })
+    .await
+}
+
+// 导出账号（csv / xlsx / ndjson），边查询边写出响应
+pub async fn export_accounts(
+    State(db): State<DatabaseConnection>,
+    principal: Principal,
+    Query(params): Query<ExportParams>,
+) -> Result<Response, AppError> {
+    let columns =
+        ExportColumn::parse_list(params.columns.as_deref()).map_err(AppError::ValidationError)?;
+    if columns.is_empty() {
+        return Err(AppError::ValidationError("columns must not be empty".to_string()));
+    }
+
+    let include_deleted = include_deleted(&principal, params.include_deleted)?;
+
+    let format = params.format;
+    let mask_api_key = !principal.has_permission(permissions::ACCOUNTS_CREDENTIALS);
+    // 导出明文 api_key 与单个账号查看凭证一样写审计
+    let exports_credentials = !mask_api_key && columns.contains(&ExportColumn::ApiKey);
+    let audit = exports_credentials.then(|| AuditContext::from_principal(&principal));
+    let rows = AccountService::export_accounts(
+        db,
+        principal.account_scope.clone(),
+        params.status.map(|status| status.is_enable()),
+        include_deleted,
+        audit,
+    );
+    let body = encode_accounts(rows, format, columns, mask_api_key).inspect_err(|e| {
+        // 响应头已发出，只能中断连接并记录日志
+        tracing::error!("Account export aborted: {}", e);
+    });
+
+    tracing::info!(
+        subject = %principal.subject,
+        format = ?format,
+        include_deleted,
+        exports_credentials,
+        "Account export started"
+    );
+
+    let filename = format!(
+        "accounts-{}.{}",
+        chrono::Local::now().format("%Y%m%d"),
+        format.extension()
+    );
+    let disposition = format!("attachment; filename=\"{}\"", filename);
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, HeaderValue::from_static(format.content_type())),
+            (
+                header::CONTENT_DISPOSITION,
+                HeaderValue::from_str(&disposition)
+                    .map_err(|e| AppError::ServiceError(e.to_string()))?,
+            ),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response())
+}
+
+// 导入账号（csv / ndjson），按 client_id 新建或更新，返回逐行报告
+pub async fn import_accounts(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Query(params): Query<ImportParams>,
+    body: Bytes,
+) -> Result<Json<ImportResponse>, AppError> {
+    let rows = parse_rows(params.format, &body).map_err(AppError::ValidationError)?;
+
+    // 指定负责人与单独调整负责人需要相同的权限
+    let sets_owner = rows
+        .iter()
+        .any(|parsed| parsed.row.as_ref().is_ok_and(|row| row.user_name.is_some()));
+    if sets_owner && !principal.has_permission(permissions::ACCOUNTS_ASSIGN) {
+        return Err(AppError::Forbidden(format!(
+            "missing permission: {}",
+            permissions::ACCOUNTS_ASSIGN
+        )));
+    }
+
+    // api_key 只能由有凭证权限的调用方写入
+    let sets_api_key = rows
+        .iter()
+        .any(|parsed| parsed.row.as_ref().is_ok_and(|row| row.api_key.is_some()));
+    if sets_api_key && !principal.has_permission(permissions::ACCOUNTS_CREDENTIALS) {
+        return Err(AppError::Forbidden(format!(
+            "missing permission: {}",
+            permissions::ACCOUNTS_CREDENTIALS
+        )));
+    }
+
+    let ctx = AuditContext::from_principal(&principal);
+    let response =
+        AccountService::import_accounts(&db, &cache, &principal.account_scope, &ctx, rows, &params)
+            .await?;
+
+    tracing::info!(
+        subject = %principal.subject,
+        dry_run = response.dry_run,
+        created = response.created,
+        updated = response.updated,
+        skipped = response.skipped,
+        failed = response.failed,
+        "Account import finished"
+    );
+
+    Ok(Json(response))
+}
+
+// 获取单个账号，响应头携带 ETag 用于后续修改的 If-Match
+pub async fn get_account(
+    State(db): State<DatabaseConnection>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    Query(deleted): Query<IncludeDeletedParams>,
+) -> Result<impl IntoResponse, AppError> {
+    let include_deleted = include_deleted(&principal, deleted.include_deleted)?;
+    let model = AccountService::get_account_with_deleted(&db, &principal.account_scope, id, include_deleted)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("account {} not found", id)))?;
+
+    Ok(versioned_response(model, &principal))
+}
+
+// 查看账号的 Ozon 凭证（明文）
+pub async fn get_account_credentials(
+    State(db): State<DatabaseConnection>,
+    principal: Principal,
+    Path(id): Path<u32>,
+) -> Result<Json<AccountCredentialsResponse>, AppError> {
+    let model = AccountService::get_account_by_id(&db, &principal.account_scope, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("account {} not found", id)))?;
+
+    let ctx = AuditContext::from_principal(&principal);
+    AuditService::record(&db, &ctx, id, actions::CREDENTIALS_VIEWED, None, None).await?;
+
+    tracing::info!(subject = %principal.subject, account_id = id, "Account credentials revealed");
+    Ok(Json(AccountCredentialsResponse::from(model)))
+}
+
+// 修改账号基础信息（需要 If-Match）
+pub async fn update_account(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+    Json(request): Json<UpdateAccountRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    request.validate().map_err(AppError::ValidationError)?;
+    let expected = expected_version(&headers, id)?;
+
+    let ctx = AuditContext::from_principal(&principal);
+    let result =
+        AccountService::update_account(&db, &cache, &principal.account_scope, &ctx, id, expected, &request)
+            .await?;
+
+    Ok(versioned_response(updated_model(result, id)?, &principal))
+}
+
+// 启用账号（需要 If-Match）
+pub async fn enable_account(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    set_enabled(&db, &cache, &principal, id, &headers, true).await
+}
+
+// 禁用账号（需要 If-Match）
+pub async fn disable_account(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    set_enabled(&db, &cache, &principal, id, &headers, false).await
+}
+
+// 调整账号负责人（需要 If-Match）
+pub async fn reassign_account_owner(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+    Json(request): Json<ReassignOwnerRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let user_name = request.user_name.trim();
+    if user_name.is_empty() {
+        return Err(AppError::ValidationError("user_name must not be empty".to_string()));
+    }
+    let expected = expected_version(&headers, id)?;
+
+    let ctx = AuditContext::from_principal(&principal);
+    let result =
+        AccountService::reassign_owner(&db, &cache, &principal.account_scope, &ctx, id, expected, user_name)
+            .await?;
+    let model = updated_model(result, id)?;
+
+    tracing::info!(subject = %principal.subject, account_id = id, user_name, "Account owner reassigned");
+
+    Ok(versioned_response(model, &principal))
+}
+
+// 批量启用/禁用/调整负责人/修改货币，支持按 ID 或条件选择账号以及 dry_run 预览
+pub async fn bulk_update_accounts(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Json(request): Json<BulkRequest>,
+) -> Result<Json<BulkResponse>, AppError> {
+    request.validate().map_err(AppError::ValidationError)?;
+    if request.requires_assign() && !principal.has_permission(permissions::ACCOUNTS_ASSIGN) {
+        return Err(AppError::Forbidden(format!(
+            "missing permission: {}",
+            permissions::ACCOUNTS_ASSIGN
+        )));
+    }
+
+    let ctx = AuditContext::from_principal(&principal);
+    let response =
+        AccountService::bulk_update(&db, &cache, &principal.account_scope, &ctx, &request).await?;
+
+    tracing::info!(
+        subject = %principal.subject,
+        dry_run = response.dry_run,
+        committed = response.committed,
+        updated = response.updated,
+        failed = response.failed,
+        "Bulk account update finished"
+    );
+
+    Ok(Json(response))
+}
+
+// 可疑的重复账号报告，按 client_id、账号名称、公司名称分组
+pub async fn find_duplicate_accounts(
+    State(db): State<DatabaseConnection>,
+    principal: Principal,
+    Query(params): Query<DuplicateParams>,
+) -> Result<Json<DuplicatesResponse>, AppError> {
+    Ok(Json(AccountService::find_duplicates(&db, &principal.account_scope, params.field).await?))
+}
+
+// api_key 在指定天数内过期（含已过期）的账号
+pub async fn list_expiring_accounts(
+    State(db): State<DatabaseConnection>,
+    State(config): State<Arc<AppConfig>>,
+    principal: Principal,
+    Query(params): Query<ExpiringParams>,
+) -> Result<Json<AccountsListResponse>, AppError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(20).clamp(1, 100);
+    let within_days = params
+        .within_days
+        .unwrap_or(config.credential_rotation.expiring_within_days);
+
+    let now = chrono::Utc::now().timestamp() as u32;
+    let before = now.saturating_add(within_days.saturating_mul(86_400));
+
+    let mut response =
+        AccountService::get_expiring_accounts(&db, &principal.account_scope, before, page, page_size).await?;
+    mask_unless_permitted(&principal, &mut response);
+    Ok(Json(response))
+}
+
+// 暂存待启用的新 api_key（需要 If-Match），之后需校验通过才能启用
+pub async fn stage_pending_key(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+    Json(request): Json<StageKeyRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    request
+        .validate(chrono::Utc::now().timestamp() as u32)
+        .map_err(AppError::ValidationError)?;
+    let expected = expected_version(&headers, id)?;
+
+    let ctx = AuditContext::from_principal(&principal);
+    let result =
+        RotationService::stage_key(&db, &cache, &principal.account_scope, &ctx, id, expected, &request).await?;
+
+    Ok(versioned_response(updated_model(result, id)?, &principal))
+}
+
+// 丢弃暂存的新 api_key（需要 If-Match）
+pub async fn cancel_pending_key(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let expected = expected_version(&headers, id)?;
+
+    let ctx = AuditContext::from_principal(&principal);
+    let result =
+        RotationService::cancel_pending(&db, &cache, &principal.account_scope, &ctx, id, expected).await?;
+
+    Ok(versioned_response(updated_model(result, id)?, &principal))
+}
+
+// 用暂存的新 api_key 调用 Ozon 校验，结果记录在 pending_verify_status
+pub async fn verify_pending_key(
+    State(db): State<DatabaseConnection>,
+    State(ozon): State<OzonClient>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+) -> Result<impl IntoResponse, AppError> {
+    let ctx = AuditContext::from_principal(&principal);
+    // 请求内同步等待 Ozon，使用不重试的短超时客户端
+    let ozon = ozon.for_verification();
+    let result =
+        RotationService::verify_pending(&db, &ozon, &cache, &principal.account_scope, &ctx, id).await?;
+    let model = updated_model(result, id)?;
+
+    tracing::info!(
+        subject = %principal.subject,
+        account_id = id,
+        pending_verify_status = ?model.pending_verify_status,
+        "Pending api key verified"
+    );
+
+    Ok(versioned_response(model, &principal))
+}
+
+// 启用已校验的新 api_key（需要 If-Match），旧 key 在宽限期内保留
+pub async fn promote_pending_key(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    State(config): State<Arc<AppConfig>>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let expected = expected_version(&headers, id)?;
+
+    let ctx = AuditContext::from_principal(&principal);
+    let grace_period = config.credential_rotation.grace_period;
+    let result =
+        RotationService::promote(&db, &cache, &principal.account_scope, &ctx, id, expected, grace_period)
+            .await?;
+
+    tracing::info!(subject = %principal.subject, account_id = id, "Account api key rotated");
+    Ok(versioned_response(updated_model(result, id)?, &principal))
+}
+
+// 宽限期内恢复轮换前的 api_key（需要 If-Match）
+pub async fn rollback_key(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let expected = expected_version(&headers, id)?;
+
+    let ctx = AuditContext::from_principal(&principal);
+    let result = RotationService::rollback(&db, &cache, &principal.account_scope, &ctx, id, expected).await?;
+
+    tracing::info!(subject = %principal.subject, account_id = id, "Account api key rolled back");
+    Ok(versioned_response(updated_model(result, id)?, &principal))
+}
+
+// 账号在本进程内的 Ozon API 调用次数、错误率和限流情况
+pub async fn get_account_api_usage(
+    State(db): State<DatabaseConnection>,
+    State(ozon): State<OzonClient>,
+    principal: Principal,
+    Path(id): Path<u32>,
+) -> Result<Json<ApiUsageResponse>, AppError> {
+    AccountService::get_account_by_id(&db, &principal.account_scope, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("account {} not found", id)))?;
+
+    let usage = ozon.usage(id);
+    let limiter = ozon.rate_limiter();
+
+    Ok(Json(ApiUsageResponse {
+        account_id: id,
+        error_rate: usage.error_rate(),
+        usage,
+        rate_limit: limiter.rate(),
+        burst: limiter.burst(),
+    }))
+}
+
+// 用保存的凭证调用 Ozon Seller API 校验是否有效，结果记录在账号上
+pub async fn verify_account(
+    State(db): State<DatabaseConnection>,
+    State(ozon): State<OzonClient>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+) -> Result<impl IntoResponse, AppError> {
+    // 请求内同步等待 Ozon，使用不重试的短超时客户端
+    let ozon = ozon.for_verification();
+    let model = VerificationService::verify_account(&db, &ozon, &cache, &principal.account_scope, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("account {} not found", id)))?;
+
+    tracing::info!(
+        subject = %principal.subject,
+        account_id = id,
+        verify_status = %model.verify_status,
+        "Account credentials verified"
+    );
+
+    Ok(versioned_response(model, &principal))
+}
+
+// 删除（归档）账号（需要 If-Match），保留期内可以恢复
+pub async fn delete_account(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let expected = expected_version(&headers, id)?;
+
+    let ctx = AuditContext::from_principal(&principal);
+    let result =
+        AccountService::delete_account(&db, &cache, &principal.account_scope, &ctx, id, expected).await?;
+    let model = updated_model(result, id)?;
+
+    tracing::info!(subject = %principal.subject, account_id = id, "Account deleted");
+
+    Ok(versioned_response(model, &principal))
+}
+
+// 恢复已删除的账号（需要 If-Match，取 ?include_deleted=true 查询到的 ETag）
+pub async fn restore_account(
+    State(db): State<DatabaseConnection>,
+    State(cache): State<AccountCache>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let expected = expected_version(&headers, id)?;
+
+    let ctx = AuditContext::from_principal(&principal);
+    let result =
+        AccountService::restore_account(&db, &cache, &principal.account_scope, &ctx, id, expected).await?;
+    let model = updated_model(result, id)?;
+
+    tracing::info!(subject = %principal.subject, account_id = id, "Account restored");
+
+    Ok(versioned_response(model, &principal))
+}
+
+async fn set_enabled(
+    db: &DatabaseConnection,
+    cache: &AccountCache,
+    principal: &Principal,
+    id: u32,
+    headers: &HeaderMap,
+    enabled: bool,
+) -> Result<impl IntoResponse, AppError> {
+    let expected = expected_version(headers, id)?;
+
+    let ctx = AuditContext::from_principal(principal);
+    let result =
+        AccountService::set_account_enabled(db, cache, &principal.account_scope, &ctx, id, expected, enabled)
+            .await?;
+
+    Ok(versioned_response(updated_model(result, id)?, principal))
+}
+
+fn updated_model(result: UpdateResult, id: u32) -> Result<account::Model, AppError> {
+    match result {
+        UpdateResult::Updated(model) => Ok(model),
+        UpdateResult::NotFound => Err(AppError::NotFound(format!("account {} not found", id))),
+        UpdateResult::Conflict(message) => Err(AppError::Conflict(message)),
+        UpdateResult::VersionMismatch(current) => Err(AppError::PreconditionFailed(format!(
+            "account {} has been modified, current ETag is {}",
+            id,
+            account_etag(current.id, current.update_time)
+        ))),
+    }
+}
+
+// 单个账号响应：带 ETag，并按权限脱敏 api_key
+fn versioned_response(model: account::Model, principal: &Principal) -> impl IntoResponse {
+    let etag = account_etag(model.id, model.update_time);
+
+    let mut response = AccountResponse::from(model);
+    if !principal.has_permission(permissions::ACCOUNTS_CREDENTIALS) {
+        response.mask_api_key();
+    }
+
+    (etag_header(&etag), Json(response))
+}
+
+// 获取单个账号的审计日志
+pub async fn get_account_audit(
+    State(db): State<DatabaseConnection>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    Query(params): Query<PaginationParams>,
+    Query(deleted): Query<IncludeDeletedParams>,
+) -> Result<Json<AuditListResponse>, AppError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(20).clamp(1, 100);
+    let include_deleted = include_deleted(&principal, deleted.include_deleted)?;
+
+    // 先确认账号在调用方的可见范围内
+    AccountService::get_account_with_deleted(&db, &principal.account_scope, id, include_deleted)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("account {} not found", id)))?;
+
+    let query = AuditQueryParams {
+        account_id: Some(id),
+        ..Default::default()
+    };
+    // 上面已确认账号可见，无需再按范围过滤
+    Ok(Json(AuditService::search(&db, &query, None, page, page_size).await?))
+}
+
+// 查看已删除的账号需要单独的权限
+fn include_deleted(principal: &Principal, requested: bool) -> Result<bool, AppError> {
+    if requested && !principal.has_permission(permissions::ACCOUNTS_DELETED) {
+        return Err(AppError::Forbidden(format!(
+            "missing permission: {}",
+            permissions::ACCOUNTS_DELETED
+        )));
+    }
+    Ok(requested)
+}
+
+// 没有凭证权限的调用方只能看到脱敏后的 api_key
+fn mask_unless_permitted(principal: &Principal, response: &mut AccountsListResponse) {
+    if !principal.has_permission(permissions::ACCOUNTS_CREDENTIALS) {
+        response.mask_api_keys();
+    }
+}
+
+
+// 列表/统计接口的条件请求处理：命中 If-None-Match / If-Modified-Since 时返回 304
+struct CacheContext<'a> {
+    principal: &'a Principal,
+    route: &'a str,
+    headers: &'a HeaderMap,
+    cache_control: &'a str,
+}
+
+impl<'a> CacheContext<'a> {
+    fn new(
+        config: &'a AppConfig,
+        principal: &'a Principal,
+        route: &'a MatchedPath,
+        headers: &'a HeaderMap,
+    ) -> Self {
+        Self {
+            principal,
+            route: route.as_str(),
+            headers,
+            cache_control: config.http_cache.cache_control_for(route.as_str()),
+        }
+    }
+
+    async fn respond<T, F, Fut>(
+        &self,
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        is_enable: Option<u8>,
+        include_deleted: bool,
+        params: &str,
+        load: F,
+    ) -> Result<Response, AppError>
+    where
+        T: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let version =
+            AccountService::collection_version(db, cache, &self.principal.account_scope, is_enable, include_deleted)
+                .await?;
+
+        // 响应内容取决于分页参数、可见范围和是否脱敏，都需要体现在 ETag 中
+        let variant = format!(
+            "{}|{}|{}|{:?}|{}",
+            self.route,
+            params,
+            include_deleted,
+            self.principal.account_scope,
+            self.principal.has_permission(permissions::ACCOUNTS_CREDENTIALS)
+        );
+        let etag = collection_etag(&version, &variant);
+
+        let mut response = if is_not_modified(self.headers, &etag, version.last_modified) {
+            StatusCode::NOT_MODIFIED.into_response()
+        } else {
+            Json(load().await?).into_response()
+        };
+
+        let headers = response.headers_mut();
+        for (name, value) in [
+            (header::ETAG, etag),
+            (header::LAST_MODIFIED, http_date(version.last_modified)),
+            (header::CACHE_CONTROL, self.cache_control.to_string()),
+            (header::VARY, "Authorization, X-API-Key".to_string()),
+        ] {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(name, value);
+            }
+        }
+
+        Ok(response)
+    }
 }
\ No newline at end of file
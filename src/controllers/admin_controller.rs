@@ -0,0 +1,81 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+use sea_orm::DatabaseConnection;
+use serde::Deserialize;
+use serde_json::json;
+use std::sync::Arc;
+use crate::{
+    auth::AccountScope,
+    config::AppConfig,
+    error::AppError,
+    logging::LogFilterHandle,
+    metrics::Metrics,
+    service::account_service::AccountService,
+};
+
+#[derive(Deserialize)]
+pub struct LogLevelRequest {
+    pub filter: String,
+}
+
+// 输出当前生效的配置（敏感字段已脱敏）
+pub async fn config_dump(
+    State(config): State<Arc<AppConfig>>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let mut config = (*config).clone();
+    if !config.database.password.is_empty() {
+        config.database.password = "******".to_string();
+    }
+    for api_key in &mut config.auth.api_keys {
+        api_key.key = "******".to_string();
+    }
+    if let Some(jwt) = config.auth.jwt.as_mut() {
+        jwt.secret = jwt.secret.as_ref().map(|_| "******".to_string());
+    }
+
+    serde_json::to_value(&config)
+        .map(Json)
+        .map_err(|e| AppError::ServiceError(e.to_string()))
+}
+
+pub async fn get_log_level(
+    State(log_filter): State<LogFilterHandle>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    Ok(Json(json!({ "filter": log_filter.current() })))
+}
+
+// 运行时调整日志过滤规则，例如 "axum_learn=debug,tower_http=info"
+pub async fn set_log_level(
+    State(log_filter): State<LogFilterHandle>,
+    Json(request): Json<LogLevelRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    log_filter
+        .set(&request.filter)
+        .map_err(AppError::ValidationError)?;
+
+    tracing::info!("Log filter changed to {}", request.filter);
+    Ok(Json(json!({ "filter": log_filter.current() })))
+}
+
+// Prometheus 文本格式指标，抓取时刷新连接池和业务指标
+pub async fn metrics(
+    State(metrics): State<Arc<Metrics>>,
+    State(db): State<DatabaseConnection>,
+) -> Result<impl IntoResponse, AppError> {
+    metrics.update_pool_stats(&db);
+
+    match AccountService::count_accounts_by_status(&db, &AccountScope::All).await {
+        Ok((enabled, disabled)) => metrics.set_account_counts(enabled, disabled),
+        Err(e) => tracing::warn!("Failed to collect account metrics: {}", e),
+    }
+
+    let body = metrics
+        .encode()
+        .map_err(|e| AppError::ServiceError(e.to_string()))?;
+
+    Ok(([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body))
+}
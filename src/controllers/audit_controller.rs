@@ -0,0 +1,28 @@
+use axum::{extract::{State, Query}, Json};
+use sea_orm::DatabaseConnection;
+use crate::auth::{permissions, Principal};
+use crate::error::AppError;
+use crate::models::audit::{AuditListResponse, AuditQueryParams};
+use crate::service::account_service::AccountService;
+use crate::service::audit_service::AuditService;
+
+// 全局审计日志查询，支持按账号、操作人、动作、请求 ID 和时间范围过滤；
+// 只返回调用方可见账号的记录，已删除账号的记录需要 accounts:deleted 权限
+pub async fn search_audit_logs(
+    State(db): State<DatabaseConnection>,
+    principal: Principal,
+    Query(params): Query<AuditQueryParams>,
+) -> Result<Json<AuditListResponse>, AppError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(20).clamp(1, 100);
+
+    if let (Some(from), Some(to)) = (params.from, params.to) {
+        if from > to {
+            return Err(AppError::ValidationError("from must be <= to".to_string()));
+        }
+    }
+
+    let include_deleted = principal.has_permission(permissions::ACCOUNTS_DELETED);
+    let visible_ids = AccountService::visible_ids(&principal.account_scope, include_deleted);
+    Ok(Json(AuditService::search(&db, &params, visible_ids, page, page_size).await?))
+}
@@ -1,16 +1,12 @@
 use crate::{
     models::fibonacci::{FibonacciQuery, FibonacciResponse},
     service::fibonacci_service::FibonacciService,
-    config::AppConfig,
     error::AppError,
 };
 use axum::{
-    extract::{Query, State},
+    extract::Query,
     Json,
 };
-use serde_json::json;
-use chrono::Utc;
-use std::sync::Arc;
 
 pub async fn fibonacci_controller(
     Query(query): Query<FibonacciQuery>,
@@ -25,18 +21,3 @@ pub async fn fibonacci_controller(
     let result = FibonacciService::get_fibonacci(n);
     Ok(Json(FibonacciResponse { n, result }))
 }
-
-pub async fn health_check(
-    State(config): State<Arc<AppConfig>>,
-) -> Result<Json<serde_json::Value>, AppError> {
-    Ok(Json(json!({
-        "status": "healthy",
-        "timestamp": Utc::now().to_rfc3339(),
-        "app": {
-            "name": config.app.name,
-            "environment": config.app.environment,
-            "debug": config.app.debug
-        },
-        "version": "0.1.0"
-    })))
-}
@@ -0,0 +1,73 @@
+use crate::{
+    build_info::build_info,
+    config::AppConfig,
+    error::AppError,
+    models::health::ReadinessResponse,
+    service::health_service::HealthService,
+    shutdown::Shutdown,
+};
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::Utc;
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub async fn health_check(
+    State(config): State<Arc<AppConfig>>,
+    State(shutdown): State<Shutdown>,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    // 优雅关闭期间返回 503，让负载均衡摘除本实例
+    let (status, health) = if shutdown.is_draining() {
+        (StatusCode::SERVICE_UNAVAILABLE, "draining")
+    } else {
+        (StatusCode::OK, "healthy")
+    };
+
+    Ok((status, Json(json!({
+        "status": health,
+        "timestamp": Utc::now().to_rfc3339(),
+        "app": {
+            "name": config.app.name,
+            "environment": config.app.environment,
+            "debug": config.app.debug
+        },
+        "build": build_info()
+    }))))
+}
+
+// 存活探针：进程能响应即视为存活，不检查外部依赖
+pub async fn liveness() -> Json<serde_json::Value> {
+    Json(json!({
+        "status": "alive",
+        "timestamp": Utc::now().to_rfc3339(),
+        "build": build_info()
+    }))
+}
+
+// 就绪探针：检查数据库连通性，任一依赖异常或正在关闭时返回 503
+pub async fn readiness(
+    State(config): State<Arc<AppConfig>>,
+    State(shutdown): State<Shutdown>,
+    State(db): State<DatabaseConnection>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let timeout = Duration::from_millis(config.database.health_check_timeout);
+    let dependencies = vec![HealthService::check_database(&db, timeout).await];
+    let all_healthy = dependencies.iter().all(|dependency| dependency.healthy);
+
+    let (status, label) = if shutdown.is_draining() {
+        (StatusCode::SERVICE_UNAVAILABLE, "draining")
+    } else if all_healthy {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "not_ready")
+    };
+
+    (status, Json(ReadinessResponse {
+        status: label.to_string(),
+        timestamp: Utc::now().to_rfc3339(),
+        dependencies,
+        pool: HealthService::pool_stats(&db),
+        build: build_info(),
+    }))
+}
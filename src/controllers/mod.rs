@@ -1,2 +1,7 @@
 pub mod fibonacci;
-pub mod account_controller;
\ No newline at end of file
+pub mod account_controller;
+pub mod admin_controller;
+pub mod audit_controller;
+pub mod health_controller;
+pub mod posting_controller;
+pub mod product_controller;
@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use sea_orm::DatabaseConnection;
+use crate::auth::Principal;
+use crate::config::AppConfig;
+use crate::error::AppError;
+use crate::models::product::{ProductQueryParams, ProductsListResponse};
+use crate::models::sync::SyncStateResponse;
+use crate::ozon::OzonClient;
+use crate::service::account_service::AccountService;
+use crate::service::product_service::ProductService;
+use crate::shutdown::Shutdown;
+use std::sync::Arc;
+
+// 查询账号在本地同步的商品
+pub async fn list_account_products(
+    State(db): State<DatabaseConnection>,
+    principal: Principal,
+    Path(id): Path<u32>,
+    Query(params): Query<ProductQueryParams>,
+) -> Result<Json<ProductsListResponse>, AppError> {
+    let page = params.page.unwrap_or(1).max(1);
+    let page_size = params.page_size.unwrap_or(20).clamp(1, 100);
+
+    // 先确认账号在调用方的可见范围内
+    AccountService::get_account_by_id(&db, &principal.account_scope, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("account {} not found", id)))?;
+
+    Ok(Json(ProductService::list_products(&db, id, &params, page, page_size).await?))
+}
+
+// 立即同步账号的商品（不等待定时任务）。同步在后台执行，
+// 返回 202 和游标状态，进度和结果通过列表接口中的 sync 字段查看
+pub async fn sync_account_products(
+    State(db): State<DatabaseConnection>,
+    State(ozon): State<OzonClient>,
+    State(config): State<Arc<AppConfig>>,
+    State(shutdown): State<Shutdown>,
+    principal: Principal,
+    Path(id): Path<u32>,
+) -> Result<(StatusCode, Json<SyncStateResponse>), AppError> {
+    let account = AccountService::get_account_by_id(&db, &principal.account_scope, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("account {} not found", id)))?;
+
+    let cursor = ProductService::start_sync(db, ozon, account, config.sync.clone(), &shutdown).await?;
+
+    tracing::info!(subject = %principal.subject, account_id = id, "Product sync triggered");
+
+    Ok((StatusCode::ACCEPTED, Json(SyncStateResponse::from(cursor))))
+}
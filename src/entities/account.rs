@@ -35,9 +35,110 @@ pub struct Model {
     
     #[sea_orm(column_name = "update_time")]
     pub update_time: u32,
+    
+    #[sea_orm(column_name = "verify_status")]
+    pub verify_status: String, // 凭证校验结果:unknown/ok/invalid/error
+    
+    #[sea_orm(column_name = "last_verified_at")]
+    pub last_verified_at: Option<u32>, // 最近一次校验时间
+    
+    #[sea_orm(column_name = "last_verify_error")]
+    pub last_verify_error: Option<String>, // 最近一次校验失败原因
+    
+    #[sea_orm(column_name = "key_expires_at")]
+    pub key_expires_at: Option<u32>, // 当前 api_key 的过期时间
+    
+    #[sea_orm(column_name = "key_rotated_at")]
+    pub key_rotated_at: Option<u32>, // 最近一次轮换时间
+    
+    #[sea_orm(column_name = "pending_api_key")]
+    pub pending_api_key: Option<String>, // 待启用的新 key
+    
+    #[sea_orm(column_name = "pending_key_expires_at")]
+    pub pending_key_expires_at: Option<u32>,
+    
+    #[sea_orm(column_name = "pending_verify_status")]
+    pub pending_verify_status: Option<String>, // 新 key 的校验结果:unknown/ok/invalid/error
+    
+    #[sea_orm(column_name = "pending_verified_at")]
+    pub pending_verified_at: Option<u32>,
+    
+    #[sea_orm(column_name = "pending_verify_error")]
+    pub pending_verify_error: Option<String>,
+    
+    #[sea_orm(column_name = "previous_api_key")]
+    pub previous_api_key: Option<String>, // 轮换前的 key，宽限期内保留
+    
+    #[sea_orm(column_name = "previous_key_expires_at")]
+    pub previous_key_expires_at: Option<u32>,
+    
+    #[sea_orm(column_name = "previous_key_valid_until")]
+    pub previous_key_valid_until: Option<u32>, // 宽限期截止时间
+    
+    #[sea_orm(column_name = "deleted_at")]
+    pub deleted_at: Option<u32>, // 删除（归档）时间，为空表示未删除
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::posting::Entity")]
+    Postings,
+    #[sea_orm(has_many = "super::product::Entity")]
+    Products,
+    #[sea_orm(has_many = "super::sync_cursor::Entity")]
+    SyncCursors,
+}
 
-impl ActiveModelBehavior for ActiveModel {}
\ No newline at end of file
+impl Related<super::posting::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Postings.def()
+    }
+}
+
+impl Related<super::product::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Products.def()
+    }
+}
+
+impl Related<super::sync_cursor::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SyncCursors.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// 测试用账号：client_id 为 100000 + id，其余字段取常见默认值，测试只覆盖关心的字段
+#[cfg(test)]
+impl Model {
+    pub fn fixture(id: u32) -> Self {
+        Self {
+            id,
+            account: format!("account-{}", id),
+            client_id: (100_000 + id).to_string(),
+            api_key: "secret-api-key".to_string(),
+            currency_code: "RUB".to_string(),
+            company_name: String::new(),
+            data: String::new(),
+            is_enable: 1,
+            user_name: "alice".to_string(),
+            create_time: 1_700_000_000,
+            update_time: 1_700_000_001,
+            verify_status: "unknown".to_string(),
+            last_verified_at: None,
+            last_verify_error: None,
+            key_expires_at: None,
+            key_rotated_at: None,
+            pending_api_key: None,
+            pending_key_expires_at: None,
+            pending_verify_status: None,
+            pending_verified_at: None,
+            pending_verify_error: None,
+            previous_api_key: None,
+            previous_key_expires_at: None,
+            previous_key_valid_until: None,
+            deleted_at: None,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sfc_ozon_account_audit")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: u64,
+    
+    #[sea_orm(column_name = "account_id")]
+    pub account_id: u32,
+    
+    #[sea_orm(column_name = "actor")]
+    pub actor: String, // 操作人（认证主体）
+    
+    #[sea_orm(column_name = "action")]
+    pub action: String, // 操作类型
+    
+    #[sea_orm(column_type = "Text")]
+    pub changes: String, // 字段变更 JSON: {"字段": {"before": .., "after": ..}}
+    
+    #[sea_orm(column_name = "request_id")]
+    pub request_id: Option<String>,
+    
+    #[sea_orm(column_name = "create_time")]
+    pub create_time: u32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
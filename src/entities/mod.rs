@@ -1 +1,5 @@
-pub mod account;
\ No newline at end of file
+pub mod account;
+pub mod account_audit;
+pub mod posting;
+pub mod product;
+pub mod sync_cursor;
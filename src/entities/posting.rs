@@ -0,0 +1,59 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sfc_ozon_posting")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: u64,
+    
+    #[sea_orm(column_name = "account_id")]
+    pub account_id: u32,
+    
+    #[sea_orm(column_name = "posting_number")]
+    pub posting_number: String, // Ozon 货件号
+    
+    #[sea_orm(column_name = "order_id")]
+    pub order_id: i64,
+    
+    #[sea_orm(column_name = "order_number")]
+    pub order_number: String,
+    
+    #[sea_orm(column_name = "status")]
+    pub status: String, // Ozon 货件状态，如 awaiting_packaging/delivering/delivered
+    
+    #[sea_orm(column_name = "in_process_at")]
+    pub in_process_at: Option<u32>, // 开始处理时间
+    
+    #[sea_orm(column_name = "shipment_date")]
+    pub shipment_date: Option<u32>, // 最晚发货时间
+    
+    #[sea_orm(column_type = "Text")]
+    pub products: String, // 商品明细 JSON
+    
+    #[sea_orm(column_name = "synced_at")]
+    pub synced_at: u32, // 最近一次同步写入时间
+    
+    #[sea_orm(column_name = "create_time")]
+    pub create_time: u32,
+    
+    #[sea_orm(column_name = "update_time")]
+    pub update_time: u32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::account::Entity",
+        from = "Column::AccountId",
+        to = "super::account::Column::Id"
+    )]
+    Account,
+}
+
+impl Related<super::account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
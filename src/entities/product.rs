@@ -0,0 +1,71 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sfc_ozon_product")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: u64,
+    
+    #[sea_orm(column_name = "account_id")]
+    pub account_id: u32,
+    
+    #[sea_orm(column_name = "product_id")]
+    pub product_id: i64, // Ozon 商品 ID
+    
+    #[sea_orm(column_name = "offer_id")]
+    pub offer_id: String, // 卖家货号
+    
+    #[sea_orm(column_name = "name")]
+    pub name: String,
+    
+    #[sea_orm(column_type = "Text")]
+    pub barcodes: String, // 逗号分隔
+    
+    #[sea_orm(column_name = "currency_code")]
+    pub currency_code: String,
+    
+    #[sea_orm(column_name = "price")]
+    pub price: String, // 与 Ozon 返回一致的字符串价格
+    
+    #[sea_orm(column_name = "old_price")]
+    pub old_price: String,
+    
+    #[sea_orm(column_name = "archived")]
+    pub archived: u8,
+    
+    #[sea_orm(column_name = "has_fbo_stocks")]
+    pub has_fbo_stocks: u8,
+    
+    #[sea_orm(column_name = "has_fbs_stocks")]
+    pub has_fbs_stocks: u8,
+    
+    #[sea_orm(column_name = "ozon_updated_at")]
+    pub ozon_updated_at: Option<String>, // Ozon 侧最后修改时间
+    
+    #[sea_orm(column_name = "synced_at")]
+    pub synced_at: u32, // 最近一次同步写入时间
+    
+    #[sea_orm(column_name = "create_time")]
+    pub create_time: u32,
+    
+    #[sea_orm(column_name = "update_time")]
+    pub update_time: u32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::account::Entity",
+        from = "Column::AccountId",
+        to = "super::account::Column::Id"
+    )]
+    Account,
+}
+
+impl Related<super::account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,56 @@
+use sea_orm::entity::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "sfc_ozon_sync_cursor")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: u32,
+    
+    #[sea_orm(column_name = "account_id")]
+    pub account_id: u32,
+    
+    #[sea_orm(column_name = "resource")]
+    pub resource: String, // 同步的数据类型，如 products/postings
+    
+    #[sea_orm(column_name = "cursor")]
+    pub cursor: String, // 断点续传位置：商品为 last_id（完整同步结束后清空），货件为已同步到的时间戳
+    
+    #[sea_orm(column_name = "status")]
+    pub status: String, // idle/running/failed
+    
+    #[sea_orm(column_name = "pass_started_at")]
+    pub pass_started_at: Option<u32>, // 本轮完整同步的开始时间（跨断点续传保持不变）
+    
+    #[sea_orm(column_name = "last_started_at")]
+    pub last_started_at: Option<u32>,
+    
+    #[sea_orm(column_name = "last_finished_at")]
+    pub last_finished_at: Option<u32>,
+    
+    #[sea_orm(column_name = "last_success_at")]
+    pub last_success_at: Option<u32>,
+    
+    #[sea_orm(column_name = "last_error")]
+    pub last_error: Option<String>,
+    
+    #[sea_orm(column_name = "items_synced")]
+    pub items_synced: u32, // 最近一轮同步写入的条数
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::account::Entity",
+        from = "Column::AccountId",
+        to = "super::account::Column::Id"
+    )]
+    Account,
+}
+
+impl Related<super::account::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Account.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
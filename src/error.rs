@@ -1,5 +1,6 @@
+use std::time::Duration;
 use axum::{
-    http::StatusCode,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -7,38 +8,102 @@ use chrono::Utc;
 use serde_json::json;
 use thiserror::Error;
 
+use crate::middleware::request_id::current_request_id;
+
 #[derive(Debug, Error)]
 #[allow(dead_code)]
 pub enum AppError {
     #[error("Validation error: {0}")]
     ValidationError(String),
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
     #[error("Resource not found: {0}")]
     NotFound(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Precondition required: {0}")]
+    PreconditionRequired(String),
+
     #[error("Service unavailable: {0}")]
     ServiceError(String),
+
+    #[error("Bad gateway: {0}")]
+    BadGateway(String),
+
+    #[error("Gateway timeout: {0}")]
+    GatewayTimeout(String),
+
+    // 上游限流，retry_after 作为 Retry-After 响应头返回
+    #[error("Upstream rate limited: {message}")]
+    UpstreamRateLimited { message: String, retry_after: Option<Duration> },
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
+        // 向上取整到秒，避免客户端提前重试
+        let retry_after = match &self {
+            AppError::UpstreamRateLimited { retry_after, .. } => {
+                retry_after.map(|d| d.as_secs() + u64::from(d.subsec_nanos() > 0))
+            }
+            _ => None,
+        };
+
         let (status, error_type, message) = match self {
             AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, "validation_error", msg),
+            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, "unauthorized", msg),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, "forbidden", msg),
             AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "not_found", msg),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, "conflict", msg),
+            AppError::PreconditionFailed(msg) => {
+                (StatusCode::PRECONDITION_FAILED, "precondition_failed", msg)
+            }
+            AppError::PreconditionRequired(msg) => {
+                (StatusCode::PRECONDITION_REQUIRED, "precondition_required", msg)
+            }
             AppError::ServiceError(msg) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, "service_error", msg)
             }
+            AppError::BadGateway(msg) => (StatusCode::BAD_GATEWAY, "upstream_error", msg),
+            AppError::GatewayTimeout(msg) => {
+                (StatusCode::GATEWAY_TIMEOUT, "upstream_timeout", msg)
+            }
+            AppError::UpstreamRateLimited { message, .. } => {
+                (StatusCode::SERVICE_UNAVAILABLE, "upstream_rate_limited", message)
+            }
         };
 
         let body = Json(json!({
             "error": {
                 "type": error_type,
                 "message": message,
-                "timestamp": Utc::now().to_rfc3339()
+                "timestamp": Utc::now().to_rfc3339(),
+                "request_id": current_request_id()
             },
             "status": status.as_u16()
         }));
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        if let Some(secs) = retry_after {
+            response.headers_mut().insert(header::RETRY_AFTER, secs.into());
+        }
+        response
+    }
+}
+
+// 数据库错误统一记录日志，响应中不暴露细节
+impl From<sea_orm::DbErr> for AppError {
+    fn from(err: sea_orm::DbErr) -> Self {
+        tracing::error!("Database error: {}", err);
+        AppError::ServiceError("database error".to_string())
     }
 }
@@ -0,0 +1,132 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use axum::http::{header, HeaderMap, HeaderValue};
+use crate::error::AppError;
+
+// 账号版本标识：由 id + update_time 组成，update_time 每次修改都会递增
+pub fn account_etag(id: u32, update_time: u32) -> String {
+    format!("\"{}-{}\"", id, update_time)
+}
+
+pub fn etag_header(etag: &str) -> [(header::HeaderName, HeaderValue); 1] {
+    [(
+        header::ETAG,
+        HeaderValue::from_str(etag).unwrap_or_else(|_| HeaderValue::from_static("\"\"")),
+    )]
+}
+
+// 解析 If-Match，返回客户端期望的 update_time；缺失返回 428，格式不符或 id 不匹配返回 412
+pub fn expected_version(headers: &HeaderMap, id: u32) -> Result<u32, AppError> {
+    let value = headers
+        .get(header::IF_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| AppError::PreconditionRequired("If-Match header is required".to_string()))?;
+
+    // If-Match 只接受强校验 ETag
+    let tag = value.trim();
+    let inner = tag
+        .strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .ok_or_else(|| AppError::PreconditionFailed(format!("invalid If-Match value: {}", tag)))?;
+
+    match inner.split_once('-') {
+        Some((tag_id, version)) if tag_id.parse() == Ok(id) => version
+            .parse()
+            .map_err(|_| AppError::PreconditionFailed(format!("invalid If-Match value: {}", tag))),
+        _ => Err(AppError::PreconditionFailed(format!(
+            "If-Match {} does not match account {}",
+            tag, id
+        ))),
+    }
+}
+
+// 列表/统计数据的版本：行数 + 最大 update_time / last_verified_at
+#[derive(Clone, Copy, Debug)]
+pub struct CollectionVersion {
+    pub count: u64,
+    pub last_modified: u32,
+}
+
+// 集合响应的弱 ETag：除数据版本外，还区分路由、查询参数和调用方可见范围
+pub fn collection_etag(version: &CollectionVersion, variant: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    variant.hash(&mut hasher);
+    format!(
+        "W/\"{:x}-{}-{}\"",
+        hasher.finish(),
+        version.count,
+        version.last_modified
+    )
+}
+
+// HTTP-date 格式，例如 "Sun, 06 Nov 1994 08:49:37 GMT"
+pub fn http_date(timestamp: u32) -> String {
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .unwrap_or_default()
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+// 判断条件请求是否命中：If-None-Match 优先（弱比较），其次 If-Modified-Since
+pub fn is_not_modified(headers: &HeaderMap, etag: &str, last_modified: u32) -> bool {
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok()) {
+        let current = strip_weak(etag);
+        return if_none_match
+            .split(',')
+            .map(str::trim)
+            .any(|candidate| candidate == "*" || strip_weak(candidate) == current);
+    }
+
+    headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| chrono::DateTime::parse_from_rfc2822(v).ok())
+        .is_some_and(|since| last_modified as i64 <= since.timestamp())
+}
+
+fn strip_weak(tag: &str) -> &str {
+    tag.strip_prefix("W/").unwrap_or(tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn parses_strong_etag() {
+        assert_eq!(expected_version(&if_match("\"7-1700000000\""), 7).unwrap(), 1_700_000_000);
+        assert_eq!(expected_version(&if_match(" \"7-5\" "), 7).unwrap(), 5);
+    }
+
+    #[test]
+    fn round_trips_account_etag() {
+        let etag = account_etag(42, 123);
+        assert_eq!(expected_version(&if_match(&etag), 42).unwrap(), 123);
+    }
+
+    #[test]
+    fn requires_if_match() {
+        let err = expected_version(&HeaderMap::new(), 7).unwrap_err();
+        assert!(matches!(err, AppError::PreconditionRequired(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn rejects_weak_or_malformed_etag() {
+        for value in ["W/\"7-5\"", "7-5", "\"7-abc\"", "\"7\"", "*"] {
+            let err = expected_version(&if_match(value), 7).unwrap_err();
+            assert!(matches!(err, AppError::PreconditionFailed(_)), "{}: {:?}", value, err);
+        }
+    }
+
+    #[test]
+    fn rejects_etag_of_other_account() {
+        let err = expected_version(&if_match("\"8-5\""), 7).unwrap_err();
+        assert!(matches!(err, AppError::PreconditionFailed(_)), "{:?}", err);
+    }
+}
@@ -0,0 +1,379 @@
+mod xlsx;
+
+use axum::body::Bytes;
+use futures::{Stream, TryStreamExt};
+use serde::Deserialize;
+use crate::entities::account;
+use crate::models::account::AccountResponse;
+use xlsx::{XlsxWriter, ZipLimitExceeded};
+
+// 累积到该大小再向客户端写出，避免每行一个 chunk
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Xlsx,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+            ExportFormat::Ndjson => "ndjson",
+        }
+    }
+}
+
+// 可导出的列，顺序即默认导出顺序
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportColumn {
+    Id,
+    Account,
+    ClientId,
+    ApiKey,
+    CurrencyCode,
+    CompanyName,
+    Data,
+    IsEnable,
+    UserName,
+    CreateTime,
+    UpdateTime,
+}
+
+impl ExportColumn {
+    pub const ALL: [ExportColumn; 11] = [
+        ExportColumn::Id,
+        ExportColumn::Account,
+        ExportColumn::ClientId,
+        ExportColumn::ApiKey,
+        ExportColumn::CurrencyCode,
+        ExportColumn::CompanyName,
+        ExportColumn::Data,
+        ExportColumn::IsEnable,
+        ExportColumn::UserName,
+        ExportColumn::CreateTime,
+        ExportColumn::UpdateTime,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportColumn::Id => "id",
+            ExportColumn::Account => "account",
+            ExportColumn::ClientId => "client_id",
+            ExportColumn::ApiKey => "api_key",
+            ExportColumn::CurrencyCode => "currency_code",
+            ExportColumn::CompanyName => "company_name",
+            ExportColumn::Data => "data",
+            ExportColumn::IsEnable => "is_enable",
+            ExportColumn::UserName => "user_name",
+            ExportColumn::CreateTime => "create_time",
+            ExportColumn::UpdateTime => "update_time",
+        }
+    }
+
+    // 解析逗号分隔的列名；未指定时导出全部列
+    pub fn parse_list(columns: Option<&str>) -> Result<Vec<ExportColumn>, String> {
+        let Some(columns) = columns.filter(|c| !c.trim().is_empty()) else {
+            return Ok(Self::ALL.to_vec());
+        };
+
+        let mut selected = Vec::new();
+        for name in columns.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            let column = Self::ALL
+                .into_iter()
+                .find(|c| c.name() == name)
+                .ok_or_else(|| format!("unknown column: {}", name))?;
+            if !selected.contains(&column) {
+                selected.push(column);
+            }
+        }
+        Ok(selected)
+    }
+
+    fn value(self, account: &AccountResponse) -> Cell<'_> {
+        match self {
+            ExportColumn::Id => Cell::Number(account.id.into()),
+            ExportColumn::Account => Cell::Text(&account.account),
+            ExportColumn::ClientId => Cell::Text(&account.client_id),
+            ExportColumn::ApiKey => Cell::Text(&account.api_key),
+            ExportColumn::CurrencyCode => Cell::Text(&account.currency_code),
+            ExportColumn::CompanyName => Cell::Text(&account.company_name),
+            ExportColumn::Data => Cell::Text(&account.data),
+            ExportColumn::IsEnable => Cell::Number(account.is_enable.into()),
+            ExportColumn::UserName => Cell::Text(&account.user_name),
+            ExportColumn::CreateTime => Cell::Number(account.create_time.into()),
+            ExportColumn::UpdateTime => Cell::Number(account.update_time.into()),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Cell<'a> {
+    Number(u64),
+    Text(&'a str),
+}
+
+// 各格式的行编码器：begin 写表头，row 写一行，finish 写结尾
+enum RowWriter {
+    Csv,
+    Ndjson,
+    Xlsx(Box<XlsxWriter>),
+}
+
+impl RowWriter {
+    fn new(format: ExportFormat) -> Self {
+        match format {
+            ExportFormat::Csv => RowWriter::Csv,
+            ExportFormat::Ndjson => RowWriter::Ndjson,
+            ExportFormat::Xlsx => RowWriter::Xlsx(Box::new(XlsxWriter::new())),
+        }
+    }
+
+    fn begin(&mut self, columns: &[ExportColumn], out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        let header: Vec<Cell> = columns.iter().map(|c| Cell::Text(c.name())).collect();
+        match self {
+            // 带 BOM，Excel 直接打开时才能正确识别 UTF-8
+            RowWriter::Csv => {
+                out.extend_from_slice("\u{feff}".as_bytes());
+                write_csv_row(&header, out);
+            }
+            RowWriter::Ndjson => {}
+            RowWriter::Xlsx(writer) => {
+                writer.begin(out)?;
+                writer.row(&header, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn row(&mut self, columns: &[ExportColumn], account: &AccountResponse, out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        let cells: Vec<Cell> = columns.iter().map(|c| c.value(account)).collect();
+        match self {
+            RowWriter::Csv => write_csv_row(&cells, out),
+            RowWriter::Ndjson => {
+                // 手动拼接以保持列顺序与请求一致
+                out.push(b'{');
+                for (i, (column, cell)) in columns.iter().zip(&cells).enumerate() {
+                    if i > 0 {
+                        out.push(b',');
+                    }
+                    write_json_string(column.name(), out);
+                    out.push(b':');
+                    match cell {
+                        Cell::Number(n) => out.extend_from_slice(n.to_string().as_bytes()),
+                        Cell::Text(s) => write_json_string(s, out),
+                    }
+                }
+                out.extend_from_slice(b"}\n");
+            }
+            RowWriter::Xlsx(writer) => writer.row(&cells, out)?,
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        match self {
+            RowWriter::Xlsx(writer) => writer.finish(out),
+            _ => Ok(()),
+        }
+    }
+}
+
+// 流已经开始输出，无法再返回错误状态码，只能以错误结束响应体
+impl From<ZipLimitExceeded> for sea_orm::DbErr {
+    fn from(_: ZipLimitExceeded) -> Self {
+        sea_orm::DbErr::Custom("xlsx export exceeds the 4 GiB zip limit, use csv or ndjson".to_string())
+    }
+}
+
+fn write_json_string(value: &str, out: &mut Vec<u8>) {
+    // 序列化 &str 不会失败
+    serde_json::to_writer(&mut *out, value).expect("serialize json string");
+}
+
+fn write_csv_row(cells: &[Cell], out: &mut Vec<u8>) {
+    for (i, cell) in cells.iter().enumerate() {
+        if i > 0 {
+            out.push(b',');
+        }
+        match cell {
+            Cell::Number(n) => out.extend_from_slice(n.to_string().as_bytes()),
+            Cell::Text(s) => write_csv_field(s, out),
+        }
+    }
+    out.extend_from_slice(b"\r\n");
+}
+
+// 以这些字符开头的文本会被表格软件当作公式
+pub const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+fn write_csv_field(value: &str, out: &mut Vec<u8>) {
+    // 以公式字符开头的文本加 ' 前缀，防止表格软件把它当公式执行；导入时会去掉该前缀
+    let guard = value.starts_with(FORMULA_PREFIXES);
+    let quote = guard || value.contains([',', '"', '\n', '\r']);
+
+    if quote {
+        out.push(b'"');
+    }
+    if guard {
+        out.push(b'\'');
+    }
+    if quote {
+        out.extend_from_slice(value.replace('"', "\"\"").as_bytes());
+        out.push(b'"');
+    } else {
+        out.extend_from_slice(value.as_bytes());
+    }
+}
+
+// 把账号行流编码成指定格式的字节流；mask_api_key 为 true 时导出脱敏后的 api_key
+pub fn encode_accounts<S>(
+    rows: S,
+    format: ExportFormat,
+    columns: Vec<ExportColumn>,
+    mask_api_key: bool,
+) -> impl Stream<Item = Result<Bytes, sea_orm::DbErr>> + Send + 'static
+where
+    S: Stream<Item = Result<account::Model, sea_orm::DbErr>> + Send + 'static,
+{
+    async_stream::try_stream! {
+        let mut writer = RowWriter::new(format);
+        let mut buffer = Vec::with_capacity(CHUNK_SIZE);
+        writer.begin(&columns, &mut buffer)?;
+
+        futures::pin_mut!(rows);
+        while let Some(model) = rows.try_next().await? {
+            let mut response = AccountResponse::from(model);
+            if mask_api_key {
+                response.mask_api_key();
+            }
+            writer.row(&columns, &response, &mut buffer)?;
+
+            if buffer.len() >= CHUNK_SIZE {
+                yield Bytes::from(std::mem::replace(&mut buffer, Vec::with_capacity(CHUNK_SIZE)));
+            }
+        }
+
+        writer.finish(&mut buffer)?;
+        yield Bytes::from(buffer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use super::*;
+
+    fn account(id: u32, account: &str, company_name: &str) -> account::Model {
+        account::Model {
+            account: account.to_string(),
+            company_name: company_name.to_string(),
+            ..account::Model::fixture(id)
+        }
+    }
+
+    async fn encode(rows: Vec<account::Model>, format: ExportFormat, columns: &str, mask_api_key: bool) -> Vec<u8> {
+        let columns = ExportColumn::parse_list(Some(columns)).unwrap();
+        let rows = futures::stream::iter(rows.into_iter().map(Ok));
+        let chunks: Vec<Bytes> = encode_accounts(rows, format, columns, mask_api_key)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        chunks.concat()
+    }
+
+    fn csv(cells: &[Cell]) -> String {
+        let mut out = Vec::new();
+        write_csv_row(cells, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn quotes_csv_fields_when_needed() {
+        assert_eq!(csv(&[Cell::Number(1), Cell::Text("plain")]), "1,plain\r\n");
+        assert_eq!(csv(&[Cell::Text("a,b"), Cell::Text("say \"hi\"")]), "\"a,b\",\"say \"\"hi\"\"\"\r\n");
+        assert_eq!(csv(&[Cell::Text("two\nlines")]), "\"two\nlines\"\r\n");
+    }
+
+    #[test]
+    fn guards_csv_formulas() {
+        assert_eq!(csv(&[Cell::Text("=SUM(A1)")]), "\"'=SUM(A1)\"\r\n");
+        assert_eq!(csv(&[Cell::Text("-1")]), "\"'-1\"\r\n");
+        assert_eq!(csv(&[Cell::Text("@cmd")]), "\"'@cmd\"\r\n");
+        assert_eq!(csv(&[Cell::Text("a=b")]), "a=b\r\n");
+    }
+
+    #[tokio::test]
+    async fn encodes_csv_with_bom_and_header() {
+        let rows = vec![account(1, "shop, main", "=HYPERLINK(\"x\")"), account(2, "second", "Acme")];
+        let out = encode(rows, ExportFormat::Csv, "id,account,company_name", false).await;
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text,
+            "\u{feff}id,account,company_name\r\n\
+             1,\"shop, main\",\"'=HYPERLINK(\"\"x\"\")\"\r\n\
+             2,second,Acme\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn encodes_ndjson_in_column_order() {
+        let rows = vec![account(1, "line\nbreak", "Acme")];
+        let out = encode(rows, ExportFormat::Ndjson, "account,id,is_enable", false).await;
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "{\"account\":\"line\\nbreak\",\"id\":1,\"is_enable\":1}\n");
+        let value: serde_json::Value = serde_json::from_str(text.trim_end()).unwrap();
+        assert_eq!(value["account"], "line\nbreak");
+    }
+
+    #[tokio::test]
+    async fn masks_api_key_when_requested() {
+        let masked = encode(vec![account(1, "a", "b")], ExportFormat::Ndjson, "api_key", true).await;
+        assert_eq!(String::from_utf8(masked).unwrap(), "{\"api_key\":\"secr****\"}\n");
+
+        let raw = encode(vec![account(1, "a", "b")], ExportFormat::Ndjson, "api_key", false).await;
+        assert_eq!(String::from_utf8(raw).unwrap(), "{\"api_key\":\"secret-api-key\"}\n");
+    }
+
+    #[tokio::test]
+    async fn streams_large_exports_in_chunks() {
+        let rows: Vec<account::Model> = (1..=5000).map(|id| account(id, "shop", "Acme")).collect();
+        let columns = ExportColumn::parse_list(None).unwrap();
+        let chunks: Vec<Bytes> = encode_accounts(futures::stream::iter(rows.into_iter().map(Ok)), ExportFormat::Csv, columns, true)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert!(chunks.len() > 1);
+        let text = String::from_utf8(chunks.concat()).unwrap();
+        assert_eq!(text.lines().count(), 5001);
+    }
+
+    #[tokio::test]
+    async fn ends_stream_with_database_error() {
+        let rows = futures::stream::iter(vec![
+            Ok(account(1, "a", "b")),
+            Err(sea_orm::DbErr::Custom("boom".to_string())),
+        ]);
+        let results: Vec<_> = encode_accounts(rows, ExportFormat::Csv, ExportColumn::ALL.to_vec(), true)
+            .collect()
+            .await;
+
+        assert!(matches!(results.last(), Some(Err(sea_orm::DbErr::Custom(message))) if message == "boom"));
+    }
+}
@@ -0,0 +1,388 @@
+use std::io::Write;
+use chrono::{Datelike, Timelike};
+use flate2::{write::DeflateEncoder, Compression};
+use super::Cell;
+
+// 最小化的 XLSX 写入：一个工作表、内联字符串，不依赖共享字符串表，
+// 因此可以边查询边输出。ZIP 条目使用 data descriptor，无需预先知道压缩后大小。
+// 不支持 ZIP64：任何大小或偏移将超过 u32 时返回 ZipLimitExceeded，而不是写出损坏的文件
+
+const CONTENT_TYPES: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+    r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+    r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+    r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+    r#"<Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+    r#"</Types>"#,
+);
+
+const ROOT_RELS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+    r#"</Relationships>"#,
+);
+
+const WORKBOOK: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+    r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+    r#"<sheets><sheet name="Accounts" sheetId="1" r:id="rId1"/></sheets>"#,
+    r#"</workbook>"#,
+);
+
+const WORKBOOK_RELS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>"#,
+    r#"</Relationships>"#,
+);
+
+const SHEET_START: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+);
+
+const SHEET_END: &str = "</sheetData></worksheet>";
+
+// 导出内容超出不带 ZIP64 的 ZIP 格式上限（4 GiB）
+#[derive(Debug)]
+pub struct ZipLimitExceeded;
+
+pub struct XlsxWriter {
+    zip: ZipWriter,
+}
+
+impl XlsxWriter {
+    pub fn new() -> Self {
+        Self { zip: ZipWriter::new() }
+    }
+
+    // 写入固定部件，并打开工作表条目
+    pub fn begin(&mut self, out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        self.zip.add_file("[Content_Types].xml", CONTENT_TYPES.as_bytes(), out)?;
+        self.zip.add_file("_rels/.rels", ROOT_RELS.as_bytes(), out)?;
+        self.zip.add_file("xl/workbook.xml", WORKBOOK.as_bytes(), out)?;
+        self.zip.add_file("xl/_rels/workbook.xml.rels", WORKBOOK_RELS.as_bytes(), out)?;
+
+        self.zip.start_file("xl/worksheets/sheet1.xml", out)?;
+        self.zip.write(SHEET_START.as_bytes(), out)
+    }
+
+    pub fn row(&mut self, cells: &[Cell], out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        let mut xml = String::from("<row>");
+        for cell in cells {
+            match cell {
+                Cell::Number(n) => {
+                    xml.push_str("<c><v>");
+                    xml.push_str(&n.to_string());
+                    xml.push_str("</v></c>");
+                }
+                Cell::Text(s) => {
+                    xml.push_str(r#"<c t="inlineStr"><is><t xml:space="preserve">"#);
+                    push_escaped(&mut xml, s);
+                    xml.push_str("</t></is></c>");
+                }
+            }
+        }
+        xml.push_str("</row>");
+
+        self.zip.write(xml.as_bytes(), out)
+    }
+
+    pub fn finish(&mut self, out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        self.zip.write(SHEET_END.as_bytes(), out)?;
+        self.zip.finish_file(out)?;
+        self.zip.finish(out)
+    }
+}
+
+// XML 转义，并丢弃 XML 1.0 不允许的控制字符
+fn push_escaped(xml: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' => xml.push_str("&quot;"),
+            '\t' | '\n' | '\r' => xml.push(c),
+            c if c < ' ' => {}
+            c => xml.push(c),
+        }
+    }
+}
+
+struct ZipEntry {
+    name: &'static str,
+    crc: u32,
+    compressed_size: u64,
+    uncompressed_size: u64,
+    offset: u64,
+}
+
+// 正在写入的条目
+struct OpenEntry {
+    name: &'static str,
+    offset: u64,
+    hasher: crc32fast::Hasher,
+    encoder: DeflateEncoder<Vec<u8>>,
+    compressed_size: u64,
+    uncompressed_size: u64,
+}
+
+// 只写不读的流式 ZIP（deflate + data descriptor，不支持 ZIP64）
+// 计数用 u64，写出前检查是否仍在 u32 范围内
+struct ZipWriter {
+    offset: u64,
+    dos_time: u16,
+    dos_date: u16,
+    entries: Vec<ZipEntry>,
+    current: Option<OpenEntry>,
+}
+
+const FLAG_DATA_DESCRIPTOR: u16 = 0x0008;
+const METHOD_DEFLATE: u16 = 8;
+const VERSION: u16 = 20;
+const ZIP_LIMIT: u64 = u32::MAX as u64;
+
+impl ZipWriter {
+    fn new() -> Self {
+        let now = chrono::Local::now();
+        let dos_time = ((now.hour() << 11) | (now.minute() << 5) | (now.second() / 2)) as u16;
+        let dos_date = (((now.year() - 1980).max(0) as u32) << 9 | now.month() << 5 | now.day()) as u16;
+
+        Self {
+            offset: 0,
+            dos_time,
+            dos_date,
+            entries: Vec::new(),
+            current: None,
+        }
+    }
+
+    fn add_file(&mut self, name: &'static str, content: &[u8], out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        self.start_file(name, out)?;
+        self.write(content, out)?;
+        self.finish_file(out)
+    }
+
+    fn start_file(&mut self, name: &'static str, out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        header.extend_from_slice(&VERSION.to_le_bytes());
+        header.extend_from_slice(&FLAG_DATA_DESCRIPTOR.to_le_bytes());
+        header.extend_from_slice(&METHOD_DEFLATE.to_le_bytes());
+        header.extend_from_slice(&self.dos_time.to_le_bytes());
+        header.extend_from_slice(&self.dos_date.to_le_bytes());
+        // CRC 和大小写在 data descriptor 中
+        header.extend_from_slice(&[0; 12]);
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(name.as_bytes());
+
+        self.current = Some(OpenEntry {
+            name,
+            offset: self.offset,
+            hasher: crc32fast::Hasher::new(),
+            encoder: DeflateEncoder::new(Vec::new(), Compression::default()),
+            compressed_size: 0,
+            uncompressed_size: 0,
+        });
+        self.emit(&header, out)
+    }
+
+    fn write(&mut self, data: &[u8], out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        let entry = self.current.as_mut().expect("zip entry not started");
+        entry.uncompressed_size += data.len() as u64;
+        if entry.uncompressed_size > ZIP_LIMIT {
+            return Err(ZipLimitExceeded);
+        }
+        entry.hasher.update(data);
+        // 写入内存 Vec 不会失败
+        entry.encoder.write_all(data).expect("deflate into memory");
+
+        let compressed = std::mem::take(entry.encoder.get_mut());
+        entry.compressed_size += compressed.len() as u64;
+        self.emit(&compressed, out)
+    }
+
+    fn finish_file(&mut self, out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        let mut entry = self.current.take().expect("zip entry not started");
+        let rest = entry.encoder.finish().expect("deflate into memory");
+        entry.compressed_size += rest.len() as u64;
+        self.emit(&rest, out)?;
+
+        let crc = entry.hasher.finalize();
+        let mut descriptor = Vec::with_capacity(16);
+        descriptor.extend_from_slice(&0x0807_4b50u32.to_le_bytes());
+        descriptor.extend_from_slice(&crc.to_le_bytes());
+        // 两个大小都不超过 offset / ZIP_LIMIT，转换为 u32 不会截断
+        descriptor.extend_from_slice(&(entry.compressed_size as u32).to_le_bytes());
+        descriptor.extend_from_slice(&(entry.uncompressed_size as u32).to_le_bytes());
+        self.emit(&descriptor, out)?;
+
+        self.entries.push(ZipEntry {
+            name: entry.name,
+            crc,
+            compressed_size: entry.compressed_size,
+            uncompressed_size: entry.uncompressed_size,
+            offset: entry.offset,
+        });
+        Ok(())
+    }
+
+    // 写中央目录和结束记录
+    fn finish(&mut self, out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        let directory_offset = self.offset;
+        let mut directory = Vec::new();
+
+        for entry in &self.entries {
+            directory.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+            directory.extend_from_slice(&VERSION.to_le_bytes());
+            directory.extend_from_slice(&VERSION.to_le_bytes());
+            directory.extend_from_slice(&FLAG_DATA_DESCRIPTOR.to_le_bytes());
+            directory.extend_from_slice(&METHOD_DEFLATE.to_le_bytes());
+            directory.extend_from_slice(&self.dos_time.to_le_bytes());
+            directory.extend_from_slice(&self.dos_date.to_le_bytes());
+            directory.extend_from_slice(&entry.crc.to_le_bytes());
+            directory.extend_from_slice(&(entry.compressed_size as u32).to_le_bytes());
+            directory.extend_from_slice(&(entry.uncompressed_size as u32).to_le_bytes());
+            directory.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            // extra、comment 长度，磁盘号，内部/外部属性
+            directory.extend_from_slice(&[0; 12]);
+            directory.extend_from_slice(&(entry.offset as u32).to_le_bytes());
+            directory.extend_from_slice(entry.name.as_bytes());
+        }
+
+        let count = self.entries.len() as u16;
+        let mut end = Vec::with_capacity(22);
+        end.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        end.extend_from_slice(&[0; 4]);
+        end.extend_from_slice(&count.to_le_bytes());
+        end.extend_from_slice(&count.to_le_bytes());
+        end.extend_from_slice(&(directory.len() as u32).to_le_bytes());
+        end.extend_from_slice(&(directory_offset as u32).to_le_bytes());
+        end.extend_from_slice(&0u16.to_le_bytes());
+
+        self.emit(&directory, out)?;
+        self.emit(&end, out)
+    }
+
+    // 所有写出都经过这里，保证 offset（以及各条目的压缩大小）不超过 ZIP_LIMIT
+    fn emit(&mut self, bytes: &[u8], out: &mut Vec<u8>) -> Result<(), ZipLimitExceeded> {
+        let offset = self.offset + bytes.len() as u64;
+        if offset > ZIP_LIMIT {
+            return Err(ZipLimitExceeded);
+        }
+        self.offset = offset;
+        out.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use flate2::read::DeflateDecoder;
+    use super::*;
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([bytes[at], bytes[at + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    // 按中央目录读出全部条目并解压，同时校验 CRC
+    fn read_zip(bytes: &[u8]) -> Vec<(String, String)> {
+        let end = bytes.len() - 22;
+        assert_eq!(u32_at(bytes, end), 0x0605_4b50);
+        let count = u16_at(bytes, end + 10) as usize;
+        let mut at = u32_at(bytes, end + 16) as usize;
+        assert_eq!(at + u32_at(bytes, end + 12) as usize, end);
+
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            assert_eq!(u32_at(bytes, at), 0x0201_4b50);
+            let crc = u32_at(bytes, at + 16);
+            let compressed_size = u32_at(bytes, at + 20) as usize;
+            let uncompressed_size = u32_at(bytes, at + 24) as usize;
+            let name_len = u16_at(bytes, at + 28) as usize;
+            let offset = u32_at(bytes, at + 42) as usize;
+            let name = String::from_utf8(bytes[at + 46..at + 46 + name_len].to_vec()).unwrap();
+
+            assert_eq!(u32_at(bytes, offset), 0x0403_4b50);
+            let data_start = offset + 30 + u16_at(bytes, offset + 26) as usize;
+            let mut content = String::new();
+            DeflateDecoder::new(&bytes[data_start..data_start + compressed_size])
+                .read_to_string(&mut content)
+                .unwrap();
+            assert_eq!(content.len(), uncompressed_size);
+            assert_eq!(crc32fast::hash(content.as_bytes()), crc);
+
+            // data descriptor 与中央目录一致
+            let descriptor = data_start + compressed_size;
+            assert_eq!(u32_at(bytes, descriptor), 0x0807_4b50);
+            assert_eq!(u32_at(bytes, descriptor + 4), crc);
+
+            entries.push((name, content));
+            at += 46 + name_len;
+        }
+        entries
+    }
+
+    #[test]
+    fn writes_readable_workbook() {
+        let mut writer = XlsxWriter::new();
+        let mut out = Vec::new();
+        writer.begin(&mut out).unwrap();
+        writer.row(&[Cell::Text("id"), Cell::Text("name")], &mut out).unwrap();
+        writer.row(&[Cell::Number(7), Cell::Text("a<b & \"c\"\u{1}")], &mut out).unwrap();
+        writer.finish(&mut out).unwrap();
+
+        let entries = read_zip(&out);
+        let names: Vec<&str> = entries.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "[Content_Types].xml",
+                "_rels/.rels",
+                "xl/workbook.xml",
+                "xl/_rels/workbook.xml.rels",
+                "xl/worksheets/sheet1.xml",
+            ]
+        );
+
+        let sheet = &entries[4].1;
+        assert!(sheet.starts_with(SHEET_START) && sheet.ends_with(SHEET_END));
+        assert!(sheet.contains("<row><c><v>7</v></c>"));
+        assert!(sheet.contains(r#"<t xml:space="preserve">a&lt;b &amp; &quot;c&quot;</t>"#));
+    }
+
+    #[test]
+    fn stops_before_exceeding_zip_limit() {
+        let mut zip = ZipWriter::new();
+        let mut out = Vec::new();
+        zip.start_file("big.xml", &mut out).unwrap();
+        zip.offset = ZIP_LIMIT - 8;
+
+        // deflate 可能先缓冲数据，超限发生在 write 或 finish_file，且超限的字节不会写出
+        let written = out.len();
+        let data: Vec<u8> = (0..64u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect();
+        let result = zip.write(&data, &mut out).and_then(|_| zip.finish_file(&mut out));
+        assert!(result.is_err());
+        assert_eq!(out.len(), written);
+    }
+
+    #[test]
+    fn rejects_entries_larger_than_zip_limit() {
+        let mut zip = ZipWriter::new();
+        let mut out = Vec::new();
+        zip.start_file("big.xml", &mut out).unwrap();
+        zip.current.as_mut().unwrap().uncompressed_size = ZIP_LIMIT;
+
+        assert!(zip.write(b"x", &mut out).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::export::FORMULA_PREFIXES;
+use crate::models::import::{ImportFormat, ImportRow, ParsedRow};
+
+// 单次导入最多的数据行数
+pub const MAX_IMPORT_ROWS: usize = 5000;
+
+// 导出文件中只读的列，导入时忽略，便于导出后修改再导回
+const IGNORED_COLUMNS: [&str; 3] = ["id", "create_time", "update_time"];
+
+const COLUMNS: [&str; 8] = [
+    "account",
+    "client_id",
+    "api_key",
+    "currency_code",
+    "company_name",
+    "data",
+    "is_enable",
+    "user_name",
+];
+
+// 解析整个导入文件；文件级错误（编码、表头、行数）直接返回，行级错误记录在对应行中
+pub fn parse_rows(format: ImportFormat, body: &[u8]) -> Result<Vec<ParsedRow>, String> {
+    let text = std::str::from_utf8(body).map_err(|_| "import file must be UTF-8".to_string())?;
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    let rows = match format {
+        ImportFormat::Csv => parse_csv(text)?,
+        ImportFormat::Ndjson => parse_ndjson(text),
+    };
+
+    if rows.is_empty() {
+        return Err("import file contains no rows".to_string());
+    }
+    if rows.len() > MAX_IMPORT_ROWS {
+        return Err(format!("at most {} rows per import", MAX_IMPORT_ROWS));
+    }
+    Ok(rows)
+}
+
+fn parse_ndjson(text: &str) -> Vec<ParsedRow> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| ParsedRow {
+            line: index + 1,
+            row: serde_json::from_str::<ImportRow>(line)
+                .map(ImportRow::normalize)
+                .map_err(|e| format!("invalid JSON: {}", e)),
+        })
+        .collect()
+}
+
+fn parse_csv(text: &str) -> Result<Vec<ParsedRow>, String> {
+    let mut records = split_csv_records(text)?.into_iter();
+    let Some((_, header)) = records.next() else {
+        return Ok(Vec::new());
+    };
+
+    // 表头列名 -> 字段，忽略列记为 None
+    let mut fields = Vec::with_capacity(header.len());
+    for name in &header {
+        let name = name.trim();
+        if IGNORED_COLUMNS.contains(&name) {
+            fields.push(None);
+        } else if let Some(field) = COLUMNS.iter().find(|c| **c == name) {
+            if fields.contains(&Some(*field)) {
+                return Err(format!("duplicate column: {}", name));
+            }
+            fields.push(Some(*field));
+        } else {
+            return Err(format!("unknown column: {}", name));
+        }
+    }
+    if !fields.contains(&Some("client_id")) {
+        return Err("missing required column: client_id".to_string());
+    }
+
+    Ok(records
+        .filter(|(_, values)| !values.iter().all(|v| v.trim().is_empty()))
+        .map(|(line, values)| ParsedRow {
+            line,
+            row: csv_record_to_row(&fields, values),
+        })
+        .collect())
+}
+
+fn csv_record_to_row(fields: &[Option<&str>], values: Vec<String>) -> Result<ImportRow, String> {
+    if values.len() != fields.len() {
+        return Err(format!("expected {} columns, found {}", fields.len(), values.len()));
+    }
+
+    let mut row = ImportRow::default();
+    for (field, value) in fields.iter().zip(values) {
+        let Some(field) = field else { continue };
+        let value = strip_formula_guard(value);
+        match *field {
+            "account" => row.account = Some(value),
+            "client_id" => row.client_id = Some(value),
+            "api_key" => row.api_key = Some(value),
+            "currency_code" => row.currency_code = Some(value),
+            "company_name" => row.company_name = Some(value),
+            "data" => row.data = Some(value),
+            "is_enable" if !value.trim().is_empty() => {
+                row.is_enable = Some(
+                    value
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("invalid is_enable: {}", value))?,
+                );
+            }
+            "user_name" => row.user_name = Some(value),
+            _ => {}
+        }
+    }
+    Ok(row.normalize())
+}
+
+// 导出 CSV 时为防止公式注入加的 ' 前缀，导入时去掉，保证导出后再导入不改变数据
+fn strip_formula_guard(value: String) -> String {
+    match value.strip_prefix('\'') {
+        Some(rest) if rest.starts_with(FORMULA_PREFIXES) => rest.to_string(),
+        _ => value,
+    }
+}
+
+// RFC 4180：逗号分隔，双引号包裹的字段可包含逗号、换行和转义的 ""。
+// 返回每条记录及其起始行号（表头为第 0 行）
+fn split_csv_records(text: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 0;
+    let mut record_line = 0;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut record)));
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("unterminated quoted field starting on line {}", record_line));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(records: &[(usize, Vec<String>)]) -> Vec<Vec<&str>> {
+        records.iter().map(|(_, r)| r.iter().map(String::as_str).collect()).collect()
+    }
+
+    #[test]
+    fn splits_quoted_comma() {
+        let records = split_csv_records("a,b\n\"x,y\",z\n").unwrap();
+        assert_eq!(fields(&records), vec![vec!["a", "b"], vec!["x,y", "z"]]);
+    }
+
+    #[test]
+    fn unescapes_doubled_quotes() {
+        let records = split_csv_records("\"say \"\"hi\"\"\",2").unwrap();
+        assert_eq!(fields(&records), vec![vec!["say \"hi\"", "2"]]);
+    }
+
+    #[test]
+    fn accepts_crlf_and_tracks_lines() {
+        let records = split_csv_records("a,b\r\n\"multi\r\nline\",c\r\nd,e\r\n").unwrap();
+        assert_eq!(
+            fields(&records),
+            vec![vec!["a", "b"], vec!["multi\r\nline", "c"], vec!["d", "e"]]
+        );
+        let lines: Vec<usize> = records.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn rejects_unterminated_quote() {
+        let err = split_csv_records("a,b\n\"open,c\n").unwrap_err();
+        assert!(err.contains("line 1"), "{}", err);
+    }
+}
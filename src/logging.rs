@@ -0,0 +1,30 @@
+use tracing_subscriber::{layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry};
+
+// 运行时可修改的日志过滤器句柄
+#[derive(Clone)]
+pub struct LogFilterHandle {
+    inner: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogFilterHandle {
+    pub fn current(&self) -> Option<String> {
+        self.inner.with_current(|filter| filter.to_string()).ok()
+    }
+
+    pub fn set(&self, directives: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+        self.inner.reload(filter).map_err(|e| e.to_string())
+    }
+}
+
+// 初始化结构化日志，返回可在运行时调整级别的句柄
+pub fn init(filter: &str) -> LogFilterHandle {
+    let (filter_layer, handle) = reload::Layer::new(EnvFilter::new(filter));
+
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+
+    LogFilterHandle { inner: handle }
+}
@@ -1,17 +1,54 @@
+mod build_info;
+mod cache;
+mod auth;
 mod config;
 mod controllers;
 mod entities;
 mod error;
+mod etag;
+mod export;
+mod import;
+mod logging;
+mod metrics;
+mod middleware;
+mod migration;
 mod models;
+mod ozon;
 mod routes;
 mod service;
+mod shutdown;
 mod state;
+mod tls;
 
-use axum::{routing::get, Router};
-use clap::Parser;
-use config::AppConfig;
-use routes::api::api_routes;
+use axum::{
+    extract::Request,
+    middleware::{from_fn, from_fn_with_state},
+    routing::get,
+    Router,
+};
+use auth::AccountScope;
+use cache::AccountCache;
+use clap::{Parser, Subcommand};
+use models::account::DuplicateField;
+use models::import::{ImportFormat, ImportParams};
+use ozon::fake::{FakeOzonOptions, Faults};
+use service::{
+    account_service::AccountService, audit_service::AuditContext,
+    retention_service::RetentionService, rotation_service::RotationService, sync_service::SyncService,
+    verification_service::VerificationService,
+};
+use config::{AdminSettings, AppConfig};
+use middleware::{
+    auth::authenticate,
+    metrics::track_metrics,
+    request_id::{request_id_middleware, RequestId},
+};
+use routes::{admin::{admin_routes, monitoring_routes}, api::api_routes};
+use shutdown::shutdown_signal;
 use state::AppState;
+use std::future::IntoFuture;
+use std::path::PathBuf;
+use std::time::Duration;
 use tower_http::{
     catch_panic::CatchPanicLayer, compression::CompressionLayer, cors::CorsLayer, trace::TraceLayer,
 };
@@ -36,6 +73,72 @@ struct Args {
     /// 配置文件路径 (可选)
     #[arg(short = 'c', long)]
     config: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// 从 CSV / NDJSON 文件导入账号，输出逐行报告后退出
+    Import {
+        /// 导入文件路径
+        file: PathBuf,
+
+        /// 文件格式，默认按扩展名判断
+        #[arg(short, long, value_enum)]
+        format: Option<ImportFormat>,
+
+        /// client_id 已存在时更新该账号
+        #[arg(long)]
+        upsert: bool,
+
+        /// 只校验和预览，不写入数据库
+        #[arg(long)]
+        dry_run: bool,
+
+        /// 审计日志中记录的操作人
+        #[arg(long, default_value = "cli")]
+        actor: String,
+    },
+
+    /// 输出重复账号报告后退出。不执行迁移，唯一索引迁移因重复数据失败时用它定位需要清理的账号
+    Duplicates {
+        /// 只检查指定字段，默认检查全部
+        #[arg(long, value_enum)]
+        field: Option<DuplicateField>,
+    },
+
+    /// 启动本地模拟的 Ozon Seller API，供开发和离线测试使用（不连接数据库）
+    FakeOzon {
+        /// 监听地址
+        #[arg(long, default_value = "127.0.0.1:8090")]
+        listen: String,
+
+        /// 允许访问的卖家凭证，格式 client_id:api_key，可重复指定
+        #[arg(long = "seller", default_value = "1000:fake-api-key")]
+        sellers: Vec<String>,
+
+        /// 每个卖家生成的商品数
+        #[arg(long, default_value_t = 50)]
+        products: u32,
+
+        /// 每个卖家生成的 FBS 货件数
+        #[arg(long, default_value_t = 100)]
+        postings: u32,
+
+        /// 每个请求附加的延迟（毫秒）
+        #[arg(long, default_value_t = 0)]
+        latency_ms: u64,
+
+        /// 每 N 个请求返回一次错误，0 表示不注入
+        #[arg(long, default_value_t = 0)]
+        fail_every: u64,
+
+        /// 注入错误时返回的状态码
+        #[arg(long, default_value_t = 500)]
+        fail_status: u16,
+    },
 }
 
 #[tokio::main]
@@ -54,10 +157,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         config.server.port = port;
     }
 
-    // 初始化结构化日志
-    tracing_subscriber::fmt()
-        .with_env_filter(config.get_log_filter())
-        .init();
+    // 初始化结构化日志（过滤规则可通过管理端点在运行时调整）
+    let log_filter = logging::init(&config.get_log_filter());
 
     // 打印配置信息（所有环境）
     // println!("🔧 Configuration loaded: {} env={}",
@@ -65,8 +166,54 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     //     config.app.environment
     // );
 
-    let state = AppState::new(config.clone()).await?;
-    
+    if let Some(Command::FakeOzon {
+        listen,
+        sellers,
+        products,
+        postings,
+        latency_ms,
+        fail_every,
+        fail_status,
+    }) = args.command
+    {
+        let faults = Faults {
+            latency_ms,
+            fail_every,
+            fail_status,
+            ..Default::default()
+        };
+        return run_fake_ozon(&listen, &sellers, products, postings, faults).await;
+    }
+
+    if let Some(Command::Duplicates { field }) = args.command {
+        return run_duplicates(&config, field).await;
+    }
+
+    let state = AppState::new(config.clone(), log_filter).await?;
+
+    if let Some(Command::Import { file, format, upsert, dry_run, actor }) = args.command {
+        return run_import(&state, file, format, upsert, dry_run, actor).await;
+    }
+
+    let shutdown = state.shutdown.clone();
+    let db = state.db.clone();
+
+    // 后台凭证健康检查，随 shutdown 协作退出
+    VerificationService::spawn_periodic_check(
+        &config.credential_check,
+        state.db.clone(),
+        state.ozon.clone(),
+        state.account_cache.clone(),
+        &shutdown,
+    );
+    SyncService::spawn_periodic_sync(&config.sync, state.db.clone(), state.ozon.clone(), &shutdown);
+    RotationService::spawn_previous_key_cleanup(state.db.clone(), state.account_cache.clone(), &shutdown);
+    RetentionService::spawn_retention_purge(
+        &config.account_retention,
+        state.db.clone(),
+        state.account_cache.clone(),
+        &shutdown,
+    );
 
 
     // 记录启用的中间件
@@ -78,6 +225,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             .route("/", get(|| async { "Hello, World!" }))
             .merge(api_routes());
 
+        // 未启用独立管理监听器时，健康检查和指标仍由公共端口提供
+        if config.server.admin.is_none() {
+            router = router.merge(monitoring_routes());
+        }
+
+        // route_layer 只作用于匹配到的路由，才能拿到 MatchedPath
+        router = router.route_layer(from_fn_with_state(state.metrics.clone(), track_metrics));
+
+        // 认证放在最内层，未认证请求也会经过 Trace 和 RequestId；
+        // 关闭认证时仍需挂载，用于注入匿名 Principal
+        router = router.layer(from_fn_with_state(state.auth.clone(), authenticate));
+        if config.auth.enabled {
+            enabled_middleware.push("Auth");
+        }
+
         // 根据配置添加中间件（按重要性顺序）
         if config.middleware.compression {
             router = router.layer(CompressionLayer::new());
@@ -90,7 +252,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
         
         if config.middleware.trace {
-            router = router.layer(TraceLayer::new_for_http());
+            router = router.layer(TraceLayer::new_for_http().make_span_with(|req: &Request| {
+                let request_id = req
+                    .extensions()
+                    .get::<RequestId>()
+                    .map(|id| id.0.as_str())
+                    .unwrap_or("-");
+                tracing::info_span!(
+                    "request",
+                    method = %req.method(),
+                    uri = %req.uri(),
+                    request_id = %request_id,
+                )
+            }));
             enabled_middleware.push("Trace");
         }
         
@@ -99,7 +273,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             enabled_middleware.push("CatchPanic");
         }
 
-        router.with_state(state)
+        // 放在最外层，保证 Trace span 和 panic 响应都能拿到 request id
+        if config.middleware.request_id {
+            router = router.layer(from_fn(request_id_middleware));
+            enabled_middleware.push("RequestId");
+        }
+
+        router.with_state(state.clone())
+    };
+
+    // 管理监听器在公共服务 drain 期间保持可用，便于观察 readiness
+    let admin_server = match &config.server.admin {
+        Some(admin) => Some(spawn_admin_server(admin, admin_routes().with_state(state)).await?),
+        None => None,
     };
 
     if !enabled_middleware.is_empty() {
@@ -111,9 +297,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let addr = &config.get_server_address();
     let listener = tokio::net::TcpListener::bind(addr).await?;
     tracing::info!(
-        "Server listening on {} (env: {})",
+        "Server listening on {} (env: {}, tls: {})",
         addr,
-        config.app.environment
+        config.app.environment,
+        config.server.tls.is_some()
     );
     tracing::info!(
         "App name: {} | Debug: {}",
@@ -121,6 +308,180 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         config.app.debug
     );
 
-    axum::serve(listener, app).await?;
+    // 收到退出信号后进入 draining 状态，停止接收新连接并等待已有请求完成
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            shutdown_signal().await;
+            shutdown.trigger();
+        }
+    });
+
+    let drain_timeout = Duration::from_secs(config.server.shutdown_timeout);
+
+    match &config.server.tls {
+        Some(tls_settings) => {
+            let rustls_config = tls::load_rustls_config(tls_settings)?;
+            tls::spawn_reload_watcher(rustls_config.clone(), tls_settings.clone(), &shutdown);
+
+            // axum-server 的 Handle 负责停止接收新连接，超时后强制关闭
+            let handle = axum_server::Handle::new();
+            tokio::spawn({
+                let handle = handle.clone();
+                let shutdown = shutdown.clone();
+                async move {
+                    shutdown.cancelled().await;
+                    handle.graceful_shutdown(Some(drain_timeout));
+                }
+            });
+
+            axum_server::from_tcp_rustls(listener.into_std()?, rustls_config)
+                .handle(handle)
+                .serve(app.into_make_service())
+                .await?;
+        }
+        None => {
+            let server = axum::serve(listener, app)
+                .with_graceful_shutdown({
+                    let shutdown = shutdown.clone();
+                    async move { shutdown.cancelled().await }
+                })
+                .into_future();
+
+            tokio::select! {
+                result = server => result?,
+                _ = async {
+                    shutdown.cancelled().await;
+                    tokio::time::sleep(drain_timeout).await;
+                } => {
+                    tracing::warn!(
+                        "Drain timeout ({}s) exceeded, dropping remaining connections",
+                        config.server.shutdown_timeout
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(admin_server) = admin_server {
+        admin_server.abort();
+    }
+
+    if !shutdown.wait_for_tasks(drain_timeout).await {
+        tracing::warn!("Background tasks did not finish within drain timeout");
+    }
+
+    db.close().await?;
+    tracing::info!("Database pool closed, shutdown complete");
+
+    Ok(())
+}
+
+// duplicates 子命令：直接连接数据库，不经过 AppState::new（其中会自动执行迁移）
+async fn run_duplicates(
+    config: &AppConfig,
+    field: Option<DuplicateField>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db = AppState::connect_database(config).await?;
+    let report = AccountService::find_all_duplicates(&db, field).await?;
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    db.close().await?;
+    Ok(())
+}
+
+// import 子命令：以不受限的可见范围执行，与 POST /accounts/import 共用同一套逻辑
+async fn run_import(
+    state: &AppState,
+    file: PathBuf,
+    format: Option<ImportFormat>,
+    upsert: bool,
+    dry_run: bool,
+    actor: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let format = format.unwrap_or_else(|| {
+        match file.extension().and_then(|ext| ext.to_str()) {
+            Some("ndjson" | "jsonl") => ImportFormat::Ndjson,
+            _ => ImportFormat::Csv,
+        }
+    });
+    let body = std::fs::read(&file)?;
+    let rows = import::parse_rows(format, &body)?;
+
+    let params = ImportParams { format, upsert, dry_run };
+    let ctx = AuditContext { actor, request_id: None };
+    // CLI 进程即将退出，缓存不需要真正生效
+    let cache = AccountCache::new(&state.config.cache, state.metrics.clone());
+    let report =
+        AccountService::import_accounts(&state.db, &cache, &AccountScope::All, &ctx, rows, &params)
+            .await?;
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    state.db.clone().close().await?;
+
+    if report.failed > 0 {
+        return Err(format!("{} row(s) failed to import", report.failed).into());
+    }
     Ok(())
 }
+
+// fake-ozon 子命令：在本地提供模拟的 Seller API，收到退出信号后停止
+async fn run_fake_ozon(
+    listen: &str,
+    sellers: &[String],
+    products: u32,
+    postings: u32,
+    faults: Faults,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let sellers = sellers
+        .iter()
+        .map(|seller| {
+            seller
+                .split_once(':')
+                .map(|(client_id, api_key)| (client_id.to_string(), api_key.to_string()))
+                .ok_or_else(|| format!("invalid seller {:?}, expected client_id:api_key", seller))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let client_ids: Vec<&str> = sellers.iter().map(|(client_id, _)| client_id.as_str()).collect();
+    tracing::info!("Fake Ozon sellers: {}", client_ids.join(", "));
+
+    let app = ozon::fake::router(FakeOzonOptions { sellers, products, postings, faults });
+
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    tracing::info!("Fake Ozon Seller API listening on {}", listen);
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    Ok(())
+}
+
+// 启动独立的管理监听器（TCP 或 Unix domain socket）
+async fn spawn_admin_server(
+    settings: &AdminSettings,
+    app: Router,
+) -> std::io::Result<tokio::task::JoinHandle<()>> {
+    #[cfg(unix)]
+    if let Some(socket_path) = &settings.socket_path {
+        // 清理上次运行残留的 socket 文件
+        let _ = std::fs::remove_file(socket_path);
+        let listener = tokio::net::UnixListener::bind(socket_path)?;
+        tracing::info!("Admin server listening on unix:{}", socket_path);
+
+        return Ok(tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                tracing::error!("Admin server error: {}", e);
+            }
+        }));
+    }
+
+    let addr = format!("{}:{}", settings.host, settings.port);
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    tracing::info!("Admin server listening on {}", addr);
+
+    Ok(tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, app).await {
+            tracing::error!("Admin server error: {}", e);
+        }
+    }))
+}
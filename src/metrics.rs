@@ -0,0 +1,125 @@
+use std::sync::Arc;
+use std::time::Duration;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use sea_orm::DatabaseConnection;
+
+// Prometheus 指标集合，克隆开销很小（内部均为 Arc）
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    pub http_requests_total: IntCounterVec,
+    pub http_request_duration_seconds: HistogramVec,
+    pub db_query_duration_seconds: HistogramVec,
+    pub db_pool_connections: IntGauge,
+    pub db_pool_idle_connections: IntGauge,
+    pub db_pool_max_connections: IntGauge,
+    pub accounts: IntGaugeVec,
+    pub cache_requests_total: IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new() -> Result<Self, prometheus::Error> {
+        let registry = Registry::new_custom(Some("axum_learn".to_string()), None)?;
+
+        let http_requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "Total number of HTTP requests"),
+            &["method", "route", "status"],
+        )?;
+        let http_request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request latency in seconds"),
+            &["method", "route"],
+        )?;
+        let db_query_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("db_query_duration_seconds", "Database statement execution time in seconds"),
+            &["status"],
+        )?;
+        let db_pool_connections =
+            IntGauge::new("db_pool_connections", "Current number of connections in the pool")?;
+        let db_pool_idle_connections =
+            IntGauge::new("db_pool_idle_connections", "Current number of idle connections in the pool")?;
+        let db_pool_max_connections =
+            IntGauge::new("db_pool_max_connections", "Configured maximum pool size")?;
+        let accounts = IntGaugeVec::new(
+            Opts::new("accounts", "Number of Ozon accounts by state"),
+            &["state"],
+        )?;
+
+        let cache_requests_total = IntCounterVec::new(
+            Opts::new("cache_requests_total", "In-process cache lookups by result"),
+            &["cache", "result"],
+        )?;
+
+        registry.register(Box::new(http_requests_total.clone()))?;
+        registry.register(Box::new(http_request_duration_seconds.clone()))?;
+        registry.register(Box::new(db_query_duration_seconds.clone()))?;
+        registry.register(Box::new(db_pool_connections.clone()))?;
+        registry.register(Box::new(db_pool_idle_connections.clone()))?;
+        registry.register(Box::new(db_pool_max_connections.clone()))?;
+        registry.register(Box::new(accounts.clone()))?;
+        registry.register(Box::new(cache_requests_total.clone()))?;
+
+        Ok(Self {
+            registry,
+            http_requests_total,
+            http_request_duration_seconds,
+            db_query_duration_seconds,
+            db_pool_connections,
+            db_pool_idle_connections,
+            db_pool_max_connections,
+            accounts,
+            cache_requests_total,
+        })
+    }
+
+    pub fn observe_http_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        self.http_requests_total
+            .with_label_values(&[method, route, &status.to_string()])
+            .inc();
+        self.http_request_duration_seconds
+            .with_label_values(&[method, route])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    // 为连接注册查询耗时回调（需在连接被克隆到各处之前调用）
+    pub fn instrument_db(self: &Arc<Self>, db: &mut DatabaseConnection) {
+        let metrics = self.clone();
+        db.set_metric_callback(move |info| {
+            let status = if info.failed { "error" } else { "ok" };
+            metrics
+                .db_query_duration_seconds
+                .with_label_values(&[status])
+                .observe(info.elapsed.as_secs_f64());
+        });
+    }
+
+    // 采集连接池当前状态。sqlx 不暴露取连接的等待时间和排队数，sea-orm 的查询回调
+    // 也不包含取连接阶段；抓取时主动取连接会在连接池打满时阻塞并加重排队，因此只导出
+    // 连接数、空闲数和上限，连接池是否吃紧看 connections 贴近 max 且 idle 为 0
+    pub fn update_pool_stats(&self, db: &DatabaseConnection) {
+        let pool = db.get_mysql_connection_pool();
+        self.db_pool_connections.set(pool.size() as i64);
+        self.db_pool_idle_connections.set(pool.num_idle() as i64);
+        self.db_pool_max_connections
+            .set(pool.options().get_max_connections() as i64);
+    }
+
+    pub fn set_account_counts(&self, enabled: u64, disabled: u64) {
+        self.accounts.with_label_values(&["enabled"]).set(enabled as i64);
+        self.accounts.with_label_values(&["disabled"]).set(disabled as i64);
+    }
+
+    pub fn observe_cache(&self, cache: &str, hit: bool) {
+        let result = if hit { "hit" } else { "miss" };
+        self.cache_requests_total.with_label_values(&[cache, result]).inc();
+    }
+
+    // 以 Prometheus 文本格式导出
+    pub fn encode(&self) -> Result<String, prometheus::Error> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        String::from_utf8(buffer).map_err(|e| prometheus::Error::Msg(e.to_string()))
+    }
+}
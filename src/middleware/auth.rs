@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::{from_fn_with_state, Next},
+    response::{IntoResponse, Response},
+    routing::MethodRouter,
+};
+use crate::auth::{Authenticator, Principal};
+use crate::error::AppError;
+use crate::state::AppState;
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+// 校验 X-API-Key 或 Authorization: Bearer，成功后把 Principal 放入 extensions
+pub async fn authenticate(
+    State(authenticator): State<Arc<Authenticator>>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    // 未启用认证时以匿名身份放行，权限守卫不会拦截
+    if !authenticator.is_enabled() {
+        req.extensions_mut().insert(Principal::anonymous());
+        return next.run(req).await;
+    }
+
+    if authenticator.is_public_path(req.uri().path()) {
+        return next.run(req).await;
+    }
+
+    let headers = req.headers();
+    let result = if let Some(key) = headers.get(API_KEY_HEADER).and_then(|v| v.to_str().ok()) {
+        authenticator.authenticate_api_key(key)
+    } else if let Some(token) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    {
+        authenticator.authenticate_bearer(token.trim())
+    } else {
+        Err(AppError::Unauthorized("missing credentials".to_string()))
+    };
+
+    match result {
+        Ok(principal) => {
+            tracing::debug!(subject = %principal.subject, "Request authenticated");
+            req.extensions_mut().insert(principal);
+            next.run(req).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
+
+// 路由级权限守卫：require(permissions::ACCOUNTS_READ, get(handler))
+pub fn require(permission: &'static str, route: MethodRouter<AppState>) -> MethodRouter<AppState> {
+    route.route_layer(from_fn_with_state(permission, check_permission))
+}
+
+async fn check_permission(
+    State(permission): State<&'static str>,
+    principal: Principal,
+    req: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    if !principal.has_permission(permission) {
+        tracing::warn!(subject = %principal.subject, permission, "Permission denied");
+        return Err(AppError::Forbidden(format!("missing permission: {}", permission)));
+    }
+
+    Ok(next.run(req).await)
+}
@@ -0,0 +1,29 @@
+use std::sync::Arc;
+use std::time::Instant;
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use crate::metrics::Metrics;
+
+// 记录请求数和耗时，route 标签使用匹配到的路由模板而不是原始路径
+pub async fn track_metrics(
+    State(metrics): State<Arc<Metrics>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let method = req.method().to_string();
+
+    let start = Instant::now();
+    let response = next.run(req).await;
+
+    metrics.observe_http_request(&method, &route, response.status().as_u16(), start.elapsed());
+
+    response
+}
@@ -0,0 +1,3 @@
+pub mod auth;
+pub mod metrics;
+pub mod request_id;
\ No newline at end of file
@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AccountAudit::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AccountAudit::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AccountAudit::AccountId).unsigned().not_null())
+                    .col(ColumnDef::new(AccountAudit::Actor).string_len(128).not_null())
+                    .col(ColumnDef::new(AccountAudit::Action).string_len(64).not_null())
+                    .col(ColumnDef::new(AccountAudit::Changes).text().not_null())
+                    .col(ColumnDef::new(AccountAudit::RequestId).string_len(128).null())
+                    .col(ColumnDef::new(AccountAudit::CreateTime).unsigned().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_audit_account_id")
+                    .table(AccountAudit::Table)
+                    .col(AccountAudit::AccountId)
+                    .col(AccountAudit::CreateTime)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_audit_create_time")
+                    .table(AccountAudit::Table)
+                    .col(AccountAudit::CreateTime)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AccountAudit::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AccountAudit {
+    #[sea_orm(iden = "sfc_ozon_account_audit")]
+    Table,
+    Id,
+    AccountId,
+    Actor,
+    Action,
+    Changes,
+    RequestId,
+    CreateTime,
+}
@@ -0,0 +1,47 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Account::Table)
+                    .add_column(
+                        ColumnDef::new(Account::VerifyStatus)
+                            .string_len(16)
+                            .not_null()
+                            .default("unknown"),
+                    )
+                    .add_column(ColumnDef::new(Account::LastVerifiedAt).unsigned().null())
+                    .add_column(ColumnDef::new(Account::LastVerifyError).string_len(512).null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Account::Table)
+                    .drop_column(Account::VerifyStatus)
+                    .drop_column(Account::LastVerifiedAt)
+                    .drop_column(Account::LastVerifyError)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Account {
+    #[sea_orm(iden = "sfc_ozon_account")]
+    Table,
+    VerifyStatus,
+    LastVerifiedAt,
+    LastVerifyError,
+}
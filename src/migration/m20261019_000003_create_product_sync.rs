@@ -0,0 +1,149 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OzonProduct::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OzonProduct::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(OzonProduct::AccountId).unsigned().not_null())
+                    .col(ColumnDef::new(OzonProduct::ProductId).big_integer().not_null())
+                    .col(ColumnDef::new(OzonProduct::OfferId).string_len(255).not_null())
+                    .col(ColumnDef::new(OzonProduct::Name).string_len(512).not_null())
+                    .col(ColumnDef::new(OzonProduct::Barcodes).text().not_null())
+                    .col(ColumnDef::new(OzonProduct::CurrencyCode).string_len(8).not_null())
+                    .col(ColumnDef::new(OzonProduct::Price).string_len(32).not_null())
+                    .col(ColumnDef::new(OzonProduct::OldPrice).string_len(32).not_null())
+                    .col(ColumnDef::new(OzonProduct::Archived).tiny_unsigned().not_null())
+                    .col(ColumnDef::new(OzonProduct::HasFboStocks).tiny_unsigned().not_null())
+                    .col(ColumnDef::new(OzonProduct::HasFbsStocks).tiny_unsigned().not_null())
+                    .col(ColumnDef::new(OzonProduct::OzonUpdatedAt).string_len(32).null())
+                    .col(ColumnDef::new(OzonProduct::SyncedAt).unsigned().not_null())
+                    .col(ColumnDef::new(OzonProduct::CreateTime).unsigned().not_null())
+                    .col(ColumnDef::new(OzonProduct::UpdateTime).unsigned().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uk_product_account_product")
+                    .table(OzonProduct::Table)
+                    .col(OzonProduct::AccountId)
+                    .col(OzonProduct::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_account_offer")
+                    .table(OzonProduct::Table)
+                    .col(OzonProduct::AccountId)
+                    .col(OzonProduct::OfferId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(SyncCursor::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SyncCursor::Id)
+                            .unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SyncCursor::AccountId).unsigned().not_null())
+                    .col(ColumnDef::new(SyncCursor::Resource).string_len(32).not_null())
+                    .col(ColumnDef::new(SyncCursor::Cursor).string_len(255).not_null())
+                    .col(ColumnDef::new(SyncCursor::Status).string_len(16).not_null())
+                    .col(ColumnDef::new(SyncCursor::PassStartedAt).unsigned().null())
+                    .col(ColumnDef::new(SyncCursor::LastStartedAt).unsigned().null())
+                    .col(ColumnDef::new(SyncCursor::LastFinishedAt).unsigned().null())
+                    .col(ColumnDef::new(SyncCursor::LastSuccessAt).unsigned().null())
+                    .col(ColumnDef::new(SyncCursor::LastError).string_len(512).null())
+                    .col(ColumnDef::new(SyncCursor::ItemsSynced).unsigned().not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uk_sync_cursor_account_resource")
+                    .table(SyncCursor::Table)
+                    .col(SyncCursor::AccountId)
+                    .col(SyncCursor::Resource)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SyncCursor::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(OzonProduct::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OzonProduct {
+    #[sea_orm(iden = "sfc_ozon_product")]
+    Table,
+    Id,
+    AccountId,
+    ProductId,
+    OfferId,
+    Name,
+    Barcodes,
+    CurrencyCode,
+    Price,
+    OldPrice,
+    Archived,
+    HasFboStocks,
+    HasFbsStocks,
+    OzonUpdatedAt,
+    SyncedAt,
+    CreateTime,
+    UpdateTime,
+}
+
+#[derive(DeriveIden)]
+enum SyncCursor {
+    #[sea_orm(iden = "sfc_ozon_sync_cursor")]
+    Table,
+    Id,
+    AccountId,
+    Resource,
+    Cursor,
+    Status,
+    PassStartedAt,
+    LastStartedAt,
+    LastFinishedAt,
+    LastSuccessAt,
+    LastError,
+    ItemsSynced,
+}
@@ -0,0 +1,95 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OzonPosting::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OzonPosting::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(OzonPosting::AccountId).unsigned().not_null())
+                    .col(ColumnDef::new(OzonPosting::PostingNumber).string_len(64).not_null())
+                    .col(ColumnDef::new(OzonPosting::OrderId).big_integer().not_null())
+                    .col(ColumnDef::new(OzonPosting::OrderNumber).string_len(64).not_null())
+                    .col(ColumnDef::new(OzonPosting::Status).string_len(64).not_null())
+                    .col(ColumnDef::new(OzonPosting::InProcessAt).unsigned().null())
+                    .col(ColumnDef::new(OzonPosting::ShipmentDate).unsigned().null())
+                    .col(ColumnDef::new(OzonPosting::Products).text().not_null())
+                    .col(ColumnDef::new(OzonPosting::SyncedAt).unsigned().not_null())
+                    .col(ColumnDef::new(OzonPosting::CreateTime).unsigned().not_null())
+                    .col(ColumnDef::new(OzonPosting::UpdateTime).unsigned().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uk_posting_account_number")
+                    .table(OzonPosting::Table)
+                    .col(OzonPosting::AccountId)
+                    .col(OzonPosting::PostingNumber)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posting_account_process")
+                    .table(OzonPosting::Table)
+                    .col(OzonPosting::AccountId)
+                    .col(OzonPosting::InProcessAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_posting_account_status_process")
+                    .table(OzonPosting::Table)
+                    .col(OzonPosting::AccountId)
+                    .col(OzonPosting::Status)
+                    .col(OzonPosting::InProcessAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OzonPosting::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OzonPosting {
+    #[sea_orm(iden = "sfc_ozon_posting")]
+    Table,
+    Id,
+    AccountId,
+    PostingNumber,
+    OrderId,
+    OrderNumber,
+    Status,
+    InProcessAt,
+    ShipmentDate,
+    Products,
+    SyncedAt,
+    CreateTime,
+    UpdateTime,
+}
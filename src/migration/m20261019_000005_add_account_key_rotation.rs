@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Account::Table)
+                    .add_column(ColumnDef::new(Account::KeyExpiresAt).unsigned().null())
+                    .add_column(ColumnDef::new(Account::KeyRotatedAt).unsigned().null())
+                    .add_column(ColumnDef::new(Account::PendingApiKey).string_len(255).null())
+                    .add_column(ColumnDef::new(Account::PendingKeyExpiresAt).unsigned().null())
+                    .add_column(ColumnDef::new(Account::PendingVerifyStatus).string_len(16).null())
+                    .add_column(ColumnDef::new(Account::PendingVerifiedAt).unsigned().null())
+                    .add_column(ColumnDef::new(Account::PendingVerifyError).string_len(512).null())
+                    .add_column(ColumnDef::new(Account::PreviousApiKey).string_len(255).null())
+                    .add_column(ColumnDef::new(Account::PreviousKeyExpiresAt).unsigned().null())
+                    .add_column(ColumnDef::new(Account::PreviousKeyValidUntil).unsigned().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_account_key_expires_at")
+                    .table(Account::Table)
+                    .col(Account::KeyExpiresAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_account_key_expires_at")
+                    .table(Account::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Account::Table)
+                    .drop_column(Account::KeyExpiresAt)
+                    .drop_column(Account::KeyRotatedAt)
+                    .drop_column(Account::PendingApiKey)
+                    .drop_column(Account::PendingKeyExpiresAt)
+                    .drop_column(Account::PendingVerifyStatus)
+                    .drop_column(Account::PendingVerifiedAt)
+                    .drop_column(Account::PendingVerifyError)
+                    .drop_column(Account::PreviousApiKey)
+                    .drop_column(Account::PreviousKeyExpiresAt)
+                    .drop_column(Account::PreviousKeyValidUntil)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Account {
+    #[sea_orm(iden = "sfc_ozon_account")]
+    Table,
+    KeyExpiresAt,
+    KeyRotatedAt,
+    PendingApiKey,
+    PendingKeyExpiresAt,
+    PendingVerifyStatus,
+    PendingVerifiedAt,
+    PendingVerifyError,
+    PreviousApiKey,
+    PreviousKeyExpiresAt,
+    PreviousKeyValidUntil,
+}
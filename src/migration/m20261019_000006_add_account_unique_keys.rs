@@ -0,0 +1,82 @@
+use sea_orm::ConnectionTrait;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 已有重复数据时唯一索引会创建失败，先给出可读的错误。
+        // 迁移失败时服务无法启动，用不执行迁移的 duplicates 子命令列出需要清理的账号
+        for column in [OzonAccount::ClientId, OzonAccount::Account] {
+            let duplicates = find_duplicates(manager, column).await?;
+            if !duplicates.is_empty() {
+                return Err(DbErr::Migration(format!(
+                    "sfc_ozon_account has duplicate {} values, resolve them before adding unique keys \
+                     (run the `duplicates` subcommand to list the accounts): {}",
+                    column.to_string(),
+                    duplicates.join(", ")
+                )));
+            }
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uk_account_client_id")
+                    .table(OzonAccount::Table)
+                    .col(OzonAccount::ClientId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("uk_account_account")
+                    .table(OzonAccount::Table)
+                    .col(OzonAccount::Account)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(Index::drop().name("uk_account_account").table(OzonAccount::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(Index::drop().name("uk_account_client_id").table(OzonAccount::Table).to_owned())
+            .await
+    }
+}
+
+// 返回最多 20 个重复的取值
+async fn find_duplicates(manager: &SchemaManager<'_>, column: OzonAccount) -> Result<Vec<String>, DbErr> {
+    let query = Query::select()
+        .column(column)
+        .from(OzonAccount::Table)
+        .group_by_col(column)
+        .and_having(Expr::col(Asterisk).count().gt(1))
+        .limit(20)
+        .to_owned();
+
+    let db = manager.get_connection();
+    let backend = db.get_database_backend();
+    let rows = db.query_all(backend.build(&query)).await?;
+
+    rows.iter()
+        .map(|row| row.try_get_by_index::<String>(0))
+        .collect()
+}
+
+#[derive(DeriveIden, Clone, Copy)]
+enum OzonAccount {
+    #[sea_orm(iden = "sfc_ozon_account")]
+    Table,
+    ClientId,
+    Account,
+}
@@ -0,0 +1,26 @@
+use sea_orm_migration::prelude::*;
+
+mod m20261019_000001_create_account_audit;
+mod m20261019_000002_add_account_verification;
+mod m20261019_000003_create_product_sync;
+mod m20261019_000004_create_posting;
+mod m20261019_000005_add_account_key_rotation;
+mod m20261019_000006_add_account_unique_keys;
+mod m20261019_000007_add_account_soft_delete;
+
+pub struct Migrator;
+
+#[async_trait::async_trait]
+impl MigratorTrait for Migrator {
+    fn migrations() -> Vec<Box<dyn MigrationTrait>> {
+        vec![
+            Box::new(m20261019_000001_create_account_audit::Migration),
+            Box::new(m20261019_000002_add_account_verification::Migration),
+            Box::new(m20261019_000003_create_product_sync::Migration),
+            Box::new(m20261019_000004_create_posting::Migration),
+            Box::new(m20261019_000005_add_account_key_rotation::Migration),
+            Box::new(m20261019_000006_add_account_unique_keys::Migration),
+            Box::new(m20261019_000007_add_account_soft_delete::Migration),
+        ]
+    }
+}
@@ -1,7 +1,10 @@
+use sea_orm::FromQueryResult;
 use serde::{Serialize, Deserialize};
 use crate::entities::account;
+use crate::export::ExportFormat;
+use crate::ozon::quota::ApiUsage;
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct AccountResponse {
     pub id: u32,
     pub account: String,
@@ -14,10 +17,27 @@ pub struct AccountResponse {
     pub user_name: String,
     pub create_time: u32,
     pub update_time: u32,
+    pub verify_status: String,
+    pub last_verified_at: Option<u32>,
+    pub last_verify_error: Option<String>,
+    pub key_expires_at: Option<u32>,
+    pub key_rotated_at: Option<u32>,
+    // 待启用的新 key 只暴露状态，不返回 key 本身
+    pub has_pending_key: bool,
+    pub pending_key_expires_at: Option<u32>,
+    pub pending_verify_status: Option<String>,
+    pub pending_verified_at: Option<u32>,
+    pub pending_verify_error: Option<String>,
+    pub previous_key_valid_until: Option<u32>,
+    // 仅 include_deleted 查询时可能有值
+    pub deleted_at: Option<u32>,
 }
 
 impl From<account::Model> for AccountResponse {
     fn from(model: account::Model) -> Self {
+        // 宽限期是否结束在读取时判断，不依赖后台清理任务是否已经执行
+        let now = chrono::Utc::now().timestamp() as u32;
+
         Self {
             id: model.id,
             account: model.account,
@@ -30,11 +50,65 @@ impl From<account::Model> for AccountResponse {
             user_name: model.user_name,
             create_time: model.create_time,
             update_time: model.update_time,
+            verify_status: model.verify_status,
+            last_verified_at: model.last_verified_at,
+            last_verify_error: model.last_verify_error,
+            key_expires_at: model.key_expires_at,
+            key_rotated_at: model.key_rotated_at,
+            has_pending_key: model.pending_api_key.is_some(),
+            pending_key_expires_at: model.pending_key_expires_at,
+            pending_verify_status: model.pending_verify_status,
+            pending_verified_at: model.pending_verified_at,
+            pending_verify_error: model.pending_verify_error,
+            previous_key_valid_until: model.previous_key_valid_until.filter(|until| *until > now),
+            deleted_at: model.deleted_at,
         }
     }
 }
 
+impl AccountResponse {
+    // 保留前 4 位，其余用 * 替换
+    pub fn mask_api_key(&mut self) {
+        self.api_key = mask_secret(&self.api_key);
+    }
+}
+
+pub fn mask_secret(secret: &str) -> String {
+    if secret.is_empty() {
+        return String::new();
+    }
+    let visible: String = secret.chars().take(4).collect();
+    format!("{}****", visible)
+}
+
 #[derive(Serialize)]
+pub struct AccountCredentialsResponse {
+    pub id: u32,
+    pub client_id: String,
+    pub api_key: String,
+    pub pending_api_key: Option<String>,
+    // 仅在宽限期内返回
+    pub previous_api_key: Option<String>,
+}
+
+impl From<account::Model> for AccountCredentialsResponse {
+    fn from(model: account::Model) -> Self {
+        let now = chrono::Utc::now().timestamp() as u32;
+        let previous_api_key = model
+            .previous_api_key
+            .filter(|_| model.previous_key_valid_until.is_some_and(|until| until > now));
+
+        Self {
+            id: model.id,
+            client_id: model.client_id,
+            api_key: model.api_key,
+            pending_api_key: model.pending_api_key,
+            previous_api_key,
+        }
+    }
+}
+
+#[derive(Clone, Serialize)]
 pub struct AccountsListResponse {
     pub total: u64,
     pub data: Vec<AccountResponse>,
@@ -43,16 +117,421 @@ pub struct AccountsListResponse {
     pub total_pages: u64,
 }
 
+impl AccountsListResponse {
+    pub fn mask_api_keys(&mut self) {
+        self.data.iter_mut().for_each(AccountResponse::mask_api_key);
+    }
+}
+
 #[derive(Deserialize)]
 pub struct PaginationParams {
     pub page: Option<u64>,
     pub page_size: Option<u64>,
 }
 
-#[derive(Serialize)]
+// ?include_deleted=true：结果包含已删除（归档）的账号，需要 accounts:deleted 权限
+#[derive(Deserialize)]
+pub struct IncludeDeletedParams {
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+// 账号列表的分页参数
+#[derive(Deserialize)]
+pub struct AccountListParams {
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+// 与 /accounts/enabled、/accounts/disabled 相同的状态过滤
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountStatus {
+    Enabled,
+    Disabled,
+}
+
+impl AccountStatus {
+    pub fn is_enable(self) -> u8 {
+        match self {
+            AccountStatus::Enabled => 1,
+            AccountStatus::Disabled => 0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ExportParams {
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub status: Option<AccountStatus>,
+    // 与列表接口相同，需要 accounts:deleted 权限
+    #[serde(default)]
+    pub include_deleted: bool,
+    // 逗号分隔的列名，未指定时导出全部列
+    pub columns: Option<String>,
+}
+
+#[derive(Clone, Serialize)]
 pub struct AccountSummaryResponse {
     pub total_accounts: u64,
     pub enabled_accounts: u64,
     pub disabled_accounts: u64,
     pub companies: Vec<String>,
-}
\ No newline at end of file
+    pub credentials: CredentialStatusSummary,
+}
+
+// 按凭证校验结果统计的账号数量
+#[derive(Clone, Default, Serialize)]
+pub struct CredentialStatusSummary {
+    pub ok: u64,
+    pub invalid: u64,
+    pub error: u64,
+    pub unknown: u64,
+}
+
+#[derive(Deserialize)]
+pub struct ReassignOwnerRequest {
+    pub user_name: String,
+}
+
+// 修改账号基础信息，未出现的字段保持不变
+#[derive(Deserialize)]
+pub struct UpdateAccountRequest {
+    pub account: Option<String>,
+    pub client_id: Option<String>,
+    pub currency_code: Option<String>,
+    pub company_name: Option<String>,
+    pub data: Option<String>,
+    // 当前 api_key 的过期时间（Unix 时间戳）
+    pub key_expires_at: Option<u32>,
+}
+
+impl UpdateAccountRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.account.is_none()
+            && self.client_id.is_none()
+            && self.currency_code.is_none()
+            && self.company_name.is_none()
+            && self.data.is_none()
+            && self.key_expires_at.is_none()
+        {
+            return Err("at least one field must be provided".to_string());
+        }
+        if self.account.as_deref().is_some_and(|v| v.trim().is_empty()) {
+            return Err("account must not be empty".to_string());
+        }
+        if self.client_id.as_deref().is_some_and(|v| v.trim().is_empty()) {
+            return Err("client_id must not be empty".to_string());
+        }
+        if self.currency_code.as_deref().is_some_and(|code| !is_currency_code(code)) {
+            return Err("currency_code must be a 3-letter ISO 4217 code".to_string());
+        }
+        Ok(())
+    }
+}
+
+// 暂存待启用的新 api_key
+#[derive(Deserialize)]
+pub struct StageKeyRequest {
+    pub api_key: String,
+    // 新 key 的过期时间（Unix 时间戳），启用后写入 key_expires_at
+    pub key_expires_at: Option<u32>,
+}
+
+impl StageKeyRequest {
+    pub fn validate(&self, now: u32) -> Result<(), String> {
+        if self.api_key.is_empty() || self.api_key.len() > 255 {
+            return Err("api_key must be 1-255 characters".to_string());
+        }
+        // 作为请求头发送，不允许空白和控制字符
+        if !self.api_key.chars().all(|c| c.is_ascii_graphic()) {
+            return Err("api_key must not contain whitespace or control characters".to_string());
+        }
+        if self.key_expires_at.is_some_and(|at| at <= now) {
+            return Err("key_expires_at must be in the future".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ExpiringParams {
+    // 默认取配置中的 credential_rotation.expiring_within_days
+    pub within_days: Option<u32>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+// 重复检测的字段
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum DuplicateField {
+    ClientId,
+    Account,
+    CompanyName,
+}
+
+impl DuplicateField {
+    pub const ALL: [DuplicateField; 3] = [Self::ClientId, Self::Account, Self::CompanyName];
+}
+
+#[derive(Deserialize)]
+pub struct DuplicateParams {
+    // 不传时检查全部字段
+    pub field: Option<DuplicateField>,
+}
+
+// 重复组中的账号，不包含凭证。只读取基础列，在后续迁移执行前也能查询
+#[derive(Clone, Serialize, FromQueryResult)]
+pub struct DuplicateAccount {
+    pub id: u32,
+    pub account: String,
+    pub client_id: String,
+    pub company_name: String,
+    pub user_name: String,
+    pub is_enable: u8,
+    pub create_time: u32,
+}
+
+#[derive(Serialize)]
+pub struct DuplicateGroup {
+    pub field: DuplicateField,
+    // 归一化后的取值（去除首尾空白；名称类字段忽略大小写和连续空白）
+    pub value: String,
+    pub accounts: Vec<DuplicateAccount>,
+}
+
+#[derive(Serialize)]
+pub struct DuplicatesResponse {
+    pub total_groups: usize,
+    pub groups: Vec<DuplicateGroup>,
+}
+
+// ISO 4217 货币代码：3 位大写字母
+pub fn is_currency_code(code: &str) -> bool {
+    code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase())
+}
+
+// 单次批量请求最多包含的操作数
+pub const MAX_BULK_OPERATIONS: usize = 100;
+
+// 批量操作执行模式
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkMode {
+    // 任一条目失败则整体回滚
+    #[default]
+    AllOrNothing,
+    // 失败的条目单独回滚，其余照常提交
+    BestEffort,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum BulkAction {
+    Enable,
+    Disable,
+    SetOwner { user_name: String },
+    SetCurrency { currency_code: String },
+}
+
+// 按条件选择账号，至少提供一个条件，避免误操作全部账号
+#[derive(Deserialize)]
+pub struct BulkFilter {
+    pub is_enable: Option<u8>,
+    pub company_name: Option<String>,
+    pub currency_code: Option<String>,
+    pub user_name: Option<String>,
+}
+
+impl BulkFilter {
+    fn is_empty(&self) -> bool {
+        self.is_enable.is_none()
+            && self.company_name.is_none()
+            && self.currency_code.is_none()
+            && self.user_name.is_none()
+    }
+}
+
+// 单个操作：ids 和 filter 二选一
+#[derive(Deserialize)]
+pub struct BulkOperation {
+    #[serde(flatten)]
+    pub action: BulkAction,
+    pub ids: Option<Vec<u32>>,
+    pub filter: Option<BulkFilter>,
+}
+
+#[derive(Deserialize)]
+pub struct BulkRequest {
+    #[serde(default)]
+    pub mode: BulkMode,
+    // 只预览受影响的账号，不落库
+    #[serde(default)]
+    pub dry_run: bool,
+    pub operations: Vec<BulkOperation>,
+}
+
+impl BulkRequest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.operations.is_empty() {
+            return Err("operations must not be empty".to_string());
+        }
+        if self.operations.len() > MAX_BULK_OPERATIONS {
+            return Err(format!("at most {} operations per request", MAX_BULK_OPERATIONS));
+        }
+
+        for (index, operation) in self.operations.iter().enumerate() {
+            match (&operation.ids, &operation.filter) {
+                (Some(ids), None) if ids.is_empty() => {
+                    return Err(format!("operations[{}]: ids must not be empty", index));
+                }
+                (None, Some(filter)) if filter.is_empty() => {
+                    return Err(format!("operations[{}]: filter must have at least one condition", index));
+                }
+                (Some(_), None) | (None, Some(_)) => {}
+                _ => {
+                    return Err(format!("operations[{}]: exactly one of ids or filter is required", index));
+                }
+            }
+
+            match &operation.action {
+                BulkAction::SetOwner { user_name } if user_name.trim().is_empty() => {
+                    return Err(format!("operations[{}]: user_name must not be empty", index));
+                }
+                BulkAction::SetCurrency { currency_code } if !is_currency_code(currency_code) => {
+                    return Err(format!(
+                        "operations[{}]: currency_code must be a 3-letter ISO 4217 code",
+                        index
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    // 调整负责人需要额外的 accounts:assign 权限
+    pub fn requires_assign(&self) -> bool {
+        self.operations
+            .iter()
+            .any(|operation| matches!(operation.action, BulkAction::SetOwner { .. }))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkItemStatus {
+    Updated,
+    // 账号已经是目标状态，无需修改
+    Unchanged,
+    NotFound,
+    Failed,
+}
+
+#[derive(Serialize)]
+pub struct BulkItemResult {
+    // 对应请求中 operations 的下标
+    pub operation: usize,
+    pub id: Option<u32>,
+    pub status: BulkItemStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct BulkResponse {
+    pub mode: BulkMode,
+    pub dry_run: bool,
+    // 是否已提交；预览或整体回滚时为 false
+    pub committed: bool,
+    pub matched: u64,
+    pub updated: u64,
+    pub failed: u64,
+    pub results: Vec<BulkItemResult>,
+}
+
+// 账号的 Ozon API 调用统计。计数保存在进程内存中，自服务启动以来累计，多实例部署时各实例独立
+#[derive(Serialize)]
+pub struct ApiUsageResponse {
+    pub account_id: u32,
+    #[serde(flatten)]
+    pub usage: ApiUsage,
+    pub error_rate: f64,
+    // 当前生效的本地限流配置（按 client_id）
+    pub rate_limit: f64,
+    pub burst: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use super::*;
+
+    fn bulk(operations: serde_json::Value) -> BulkRequest {
+        serde_json::from_value(json!({ "operations": operations })).unwrap()
+    }
+
+    fn validate_err(operations: serde_json::Value) -> String {
+        bulk(operations).validate().unwrap_err()
+    }
+
+    #[test]
+    fn accepts_ids_or_filter() {
+        let request = bulk(json!([
+            { "action": "enable", "ids": [1, 2] },
+            { "action": "set_currency", "currency_code": "RUB", "filter": { "user_name": "alice" } },
+        ]));
+        assert!(request.validate().is_ok());
+        assert!(!request.requires_assign());
+    }
+
+    #[test]
+    fn enforces_operation_count_limit() {
+        assert_eq!(validate_err(json!([])), "operations must not be empty");
+
+        let max = vec![json!({ "action": "disable", "ids": [1] }); MAX_BULK_OPERATIONS];
+        assert!(bulk(json!(max)).validate().is_ok());
+
+        let over = vec![json!({ "action": "disable", "ids": [1] }); MAX_BULK_OPERATIONS + 1];
+        assert_eq!(
+            validate_err(json!(over)),
+            format!("at most {} operations per request", MAX_BULK_OPERATIONS)
+        );
+    }
+
+    #[test]
+    fn requires_exactly_one_selector() {
+        let neither = validate_err(json!([{ "action": "enable" }]));
+        assert!(neither.contains("exactly one of ids or filter"), "{}", neither);
+
+        let both = validate_err(json!([{ "action": "enable", "ids": [1], "filter": { "is_enable": 0 } }]));
+        assert!(both.contains("exactly one of ids or filter"), "{}", both);
+
+        let empty_ids = validate_err(json!([{ "action": "enable", "ids": [] }]));
+        assert_eq!(empty_ids, "operations[0]: ids must not be empty");
+
+        let empty_filter = validate_err(json!([{ "action": "enable", "filter": {} }]));
+        assert_eq!(empty_filter, "operations[0]: filter must have at least one condition");
+    }
+
+    #[test]
+    fn validates_action_arguments() {
+        let owner = validate_err(json!([
+            { "action": "enable", "ids": [1] },
+            { "action": "set_owner", "user_name": "  ", "ids": [1] },
+        ]));
+        assert_eq!(owner, "operations[1]: user_name must not be empty");
+
+        let currency = validate_err(json!([{ "action": "set_currency", "currency_code": "rub", "ids": [1] }]));
+        assert!(currency.contains("ISO 4217"), "{}", currency);
+
+        let request = bulk(json!([{ "action": "set_owner", "user_name": "bob", "ids": [1] }]));
+        assert!(request.validate().is_ok());
+        assert!(request.requires_assign());
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Serialize, Deserialize};
+use crate::entities::account_audit;
+
+#[derive(Serialize)]
+pub struct AuditLogResponse {
+    pub id: u64,
+    pub account_id: u32,
+    pub actor: String,
+    pub action: String,
+    pub changes: serde_json::Value,
+    pub request_id: Option<String>,
+    pub create_time: u32,
+}
+
+impl From<account_audit::Model> for AuditLogResponse {
+    fn from(model: account_audit::Model) -> Self {
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            actor: model.actor,
+            action: model.action,
+            changes: serde_json::from_str(&model.changes)
+                .unwrap_or(serde_json::Value::String(model.changes)),
+            request_id: model.request_id,
+            create_time: model.create_time,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AuditListResponse {
+    pub total: u64,
+    pub data: Vec<AuditLogResponse>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+}
+
+// 审计日志查询条件，时间为 Unix 秒
+#[derive(Deserialize, Default)]
+pub struct AuditQueryParams {
+    pub account_id: Option<u32>,
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub request_id: Option<String>,
+    pub from: Option<u32>,
+    pub to: Option<u32>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
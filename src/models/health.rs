@@ -0,0 +1,29 @@
+use serde::Serialize;
+use crate::build_info::BuildInfo;
+
+#[derive(Serialize)]
+pub struct DependencyHealth {
+    pub name: String,
+    pub healthy: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PoolStats {
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    pub max: u32,
+    // 使用中连接占最大连接数的比例 (0.0 - 1.0)
+    pub saturation: f64,
+}
+
+#[derive(Serialize)]
+pub struct ReadinessResponse {
+    pub status: String,
+    pub timestamp: String,
+    pub dependencies: Vec<DependencyHealth>,
+    pub pool: PoolStats,
+    pub build: BuildInfo,
+}
@@ -0,0 +1,119 @@
+use serde::{Serialize, Deserialize};
+use crate::models::account::is_currency_code;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+#[derive(Deserialize)]
+pub struct ImportParams {
+    #[serde(default)]
+    pub format: ImportFormat,
+    // client_id 已存在时更新该账号；否则跳过
+    #[serde(default)]
+    pub upsert: bool,
+    // 完整执行后回滚，返回的报告即为预览
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+// 导入的一行；未出现的字段在更新时保持不变
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportRow {
+    pub account: Option<String>,
+    pub client_id: Option<String>,
+    pub api_key: Option<String>,
+    pub currency_code: Option<String>,
+    pub company_name: Option<String>,
+    pub data: Option<String>,
+    pub is_enable: Option<u8>,
+    pub user_name: Option<String>,
+}
+
+impl ImportRow {
+    // 去掉首尾空白，空字符串视为未提供
+    pub fn normalize(mut self) -> Self {
+        for field in [
+            &mut self.account,
+            &mut self.client_id,
+            &mut self.api_key,
+            &mut self.currency_code,
+            &mut self.company_name,
+            &mut self.user_name,
+        ] {
+            *field = field.take().map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        }
+        self
+    }
+
+    // creating 为 true 时校验新建账号的必填字段
+    pub fn validate(&self, creating: bool) -> Result<(), String> {
+        if self.client_id.is_none() {
+            return Err("client_id is required".to_string());
+        }
+        if creating {
+            for (name, value) in [
+                ("account", &self.account),
+                ("api_key", &self.api_key),
+                ("currency_code", &self.currency_code),
+            ] {
+                if value.is_none() {
+                    return Err(format!("{} is required", name));
+                }
+            }
+        }
+        // 没有凭证权限时导出的是脱敏后的 api_key，不能导回
+        if self.api_key.as_deref().is_some_and(|key| key.ends_with("****")) {
+            return Err("api_key looks masked, export with accounts:credentials or drop the column".to_string());
+        }
+        if self.currency_code.as_deref().is_some_and(|code| !is_currency_code(code)) {
+            return Err("currency_code must be a 3-letter ISO 4217 code".to_string());
+        }
+        if self.is_enable.is_some_and(|v| v > 1) {
+            return Err("is_enable must be 0 or 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+// 解析后的一行：line 为源文件中的行号（从 1 开始，不含表头）
+pub struct ParsedRow {
+    pub line: usize,
+    pub row: Result<ImportRow, String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    Created,
+    Updated,
+    Skipped,
+    Failed,
+}
+
+#[derive(Serialize)]
+pub struct ImportRowResult {
+    pub line: usize,
+    pub client_id: Option<String>,
+    pub id: Option<u32>,
+    pub status: ImportRowStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ImportResponse {
+    pub dry_run: bool,
+    pub upsert: bool,
+    // 是否已提交；预览时为 false
+    pub committed: bool,
+    pub created: u64,
+    pub updated: u64,
+    pub skipped: u64,
+    pub failed: u64,
+    pub rows: Vec<ImportRowResult>,
+}
@@ -1,2 +1,8 @@
 pub mod fibonacci;
-pub mod account;
\ No newline at end of file
+pub mod account;
+pub mod audit;
+pub mod import;
+pub mod posting;
+pub mod product;
+pub mod sync;
+pub mod health;
\ No newline at end of file
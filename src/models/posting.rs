@@ -0,0 +1,55 @@
+use serde::{Serialize, Deserialize};
+use crate::entities::posting;
+use crate::models::sync::SyncStateResponse;
+
+#[derive(Serialize)]
+pub struct PostingResponse {
+    pub id: u64,
+    pub account_id: u32,
+    pub posting_number: String,
+    pub order_id: i64,
+    pub order_number: String,
+    pub status: String,
+    pub in_process_at: Option<u32>,
+    pub shipment_date: Option<u32>,
+    pub products: serde_json::Value,
+    pub synced_at: u32,
+}
+
+impl From<posting::Model> for PostingResponse {
+    fn from(model: posting::Model) -> Self {
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            posting_number: model.posting_number,
+            order_id: model.order_id,
+            order_number: model.order_number,
+            status: model.status,
+            in_process_at: model.in_process_at,
+            shipment_date: model.shipment_date,
+            products: serde_json::from_str(&model.products).unwrap_or(serde_json::Value::Null),
+            synced_at: model.synced_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PostingsListResponse {
+    pub total: u64,
+    pub data: Vec<PostingResponse>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+    // 账号尚未同步过时为 null
+    pub sync: Option<SyncStateResponse>,
+}
+
+#[derive(Deserialize)]
+pub struct PostingQueryParams {
+    pub status: Option<String>,
+    // 按 in_process_at 过滤的时间范围（Unix 时间戳，闭区间）
+    pub from: Option<u32>,
+    pub to: Option<u32>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
@@ -0,0 +1,68 @@
+use serde::{Serialize, Deserialize};
+use crate::entities::product;
+use crate::models::sync::SyncStateResponse;
+
+#[derive(Serialize)]
+pub struct ProductResponse {
+    pub id: u64,
+    pub account_id: u32,
+    pub product_id: i64,
+    pub offer_id: String,
+    pub name: String,
+    pub barcodes: Vec<String>,
+    pub currency_code: String,
+    pub price: String,
+    pub old_price: String,
+    pub archived: bool,
+    pub has_fbo_stocks: bool,
+    pub has_fbs_stocks: bool,
+    pub ozon_updated_at: Option<String>,
+    pub synced_at: u32,
+}
+
+impl From<product::Model> for ProductResponse {
+    fn from(model: product::Model) -> Self {
+        Self {
+            id: model.id,
+            account_id: model.account_id,
+            product_id: model.product_id,
+            offer_id: model.offer_id,
+            name: model.name,
+            barcodes: model
+                .barcodes
+                .split(',')
+                .filter(|b| !b.is_empty())
+                .map(str::to_string)
+                .collect(),
+            currency_code: model.currency_code,
+            price: model.price,
+            old_price: model.old_price,
+            archived: model.archived == 1,
+            has_fbo_stocks: model.has_fbo_stocks == 1,
+            has_fbs_stocks: model.has_fbs_stocks == 1,
+            ozon_updated_at: model.ozon_updated_at,
+            synced_at: model.synced_at,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ProductsListResponse {
+    pub total: u64,
+    pub data: Vec<ProductResponse>,
+    pub page: u64,
+    pub page_size: u64,
+    pub total_pages: u64,
+    // 账号尚未同步过时为 null
+    pub sync: Option<SyncStateResponse>,
+}
+
+#[derive(Deserialize)]
+pub struct ProductQueryParams {
+    pub offer_id: Option<String>,
+    pub archived: Option<bool>,
+    // 按商品名称模糊匹配
+    pub name: Option<String>,
+    pub page: Option<u64>,
+    pub page_size: Option<u64>,
+}
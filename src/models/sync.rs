@@ -0,0 +1,30 @@
+use serde::Serialize;
+use crate::entities::sync_cursor;
+
+// 同步游标状态
+#[derive(Serialize)]
+pub struct SyncStateResponse {
+    pub resource: String,
+    pub status: String,
+    pub cursor: String,
+    pub last_started_at: Option<u32>,
+    pub last_finished_at: Option<u32>,
+    pub last_success_at: Option<u32>,
+    pub last_error: Option<String>,
+    pub items_synced: u32,
+}
+
+impl From<sync_cursor::Model> for SyncStateResponse {
+    fn from(model: sync_cursor::Model) -> Self {
+        Self {
+            resource: model.resource,
+            status: model.status,
+            cursor: model.cursor,
+            last_started_at: model.last_started_at,
+            last_finished_at: model.last_finished_at,
+            last_success_at: model.last_success_at,
+            last_error: model.last_error,
+            items_synced: model.items_synced,
+        }
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::{from_fn_with_state, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use super::models::{
+    ErrorBody, FbsPosting, FbsPostingListRequest, FbsPostingListResponse, FbsPostingListResult,
+    FbsPostingProduct, ProductInfo, ProductInfoListRequest, ProductInfoListResponse, ProductListItem,
+    ProductListRequest, ProductListResponse, ProductListResult,
+};
+use super::{API_KEY_HEADER, CLIENT_ID_HEADER};
+
+// 与 Ozon 一致的单页上限
+const MAX_LIMIT: u32 = 1000;
+
+const POSTING_STATUSES: [&str; 5] = [
+    "awaiting_packaging",
+    "awaiting_deliver",
+    "delivering",
+    "delivered",
+    "cancelled",
+];
+
+// fake-ozon 的启动参数
+pub struct FakeOzonOptions {
+    // (client_id, api_key)
+    pub sellers: Vec<(String, String)>,
+    // 每个卖家生成的商品数和货件数
+    pub products: u32,
+    pub postings: u32,
+    pub faults: Faults,
+}
+
+// 可注入的故障，启动后可通过 PUT /__fake/faults 修改
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Faults {
+    // 每个请求附加的延迟（毫秒）
+    pub latency_ms: u64,
+    // 每 N 个请求失败一次，0 表示不注入
+    pub fail_every: u64,
+    // 接下来的 N 个请求全部失败
+    pub fail_next: u64,
+    // 注入错误的状态码，0 按 500 处理
+    pub fail_status: u16,
+    // fail_status 为 429 时返回的 Retry-After（秒）
+    pub retry_after: Option<u64>,
+}
+
+#[derive(Default, Serialize)]
+struct FakeStats {
+    requests: u64,
+    injected_failures: u64,
+    auth_failures: u64,
+}
+
+struct Seller {
+    api_key: String,
+    // 按 product_id 升序
+    products: Vec<FakeProduct>,
+    // 按 in_process_at 升序
+    postings: Vec<FakePosting>,
+}
+
+struct FakeProduct {
+    info: ProductInfo,
+    has_fbo_stocks: bool,
+    has_fbs_stocks: bool,
+}
+
+struct FakePosting {
+    in_process_at: i64,
+    posting: FbsPosting,
+}
+
+#[derive(Clone)]
+struct FakeOzon {
+    sellers: Arc<HashMap<String, Seller>>,
+    faults: Arc<Mutex<Faults>>,
+    stats: Arc<Mutex<FakeStats>>,
+    counter: Arc<AtomicU64>,
+}
+
+// 通过认证的卖家，由中间件放入请求 extensions
+#[derive(Clone)]
+struct SellerId(String);
+
+// 本地模拟的 Ozon Seller API，只实现本服务调用的接口；数据在启动时按参数确定性生成
+pub fn router(options: FakeOzonOptions) -> Router {
+    let now = Utc::now().timestamp();
+    let sellers = options
+        .sellers
+        .into_iter()
+        .enumerate()
+        .map(|(index, (client_id, api_key))| {
+            let seller = seed_seller(index as i64, api_key, options.products, options.postings, now);
+            (client_id, seller)
+        })
+        .collect();
+
+    let state = FakeOzon {
+        sellers: Arc::new(sellers),
+        faults: Arc::new(Mutex::new(options.faults)),
+        stats: Arc::new(Mutex::new(FakeStats::default())),
+        counter: Arc::new(AtomicU64::new(0)),
+    };
+
+    let seller_api = Router::new()
+        .route("/v3/product/list", post(product_list))
+        .route("/v3/product/info/list", post(product_info_list))
+        .route("/v3/posting/fbs/list", post(fbs_posting_list))
+        .route_layer(from_fn_with_state(state.clone(), simulate));
+
+    Router::new()
+        .merge(seller_api)
+        .route("/__fake/faults", get(get_faults).put(put_faults))
+        .route("/__fake/stats", get(get_stats))
+        .with_state(state)
+}
+
+fn seed_seller(index: i64, api_key: String, products: u32, postings: u32, now: i64) -> Seller {
+    let base_id = (index + 1) * 1_000_000;
+
+    let products: Vec<FakeProduct> = (0..i64::from(products))
+        .map(|i| {
+            let created_at = now - (i + 1) * 86_400;
+            FakeProduct {
+                info: ProductInfo {
+                    id: base_id + i,
+                    name: format!("Fake product {}", i + 1),
+                    offer_id: format!("FAKE-{}-{:05}", index + 1, i + 1),
+                    barcodes: vec![format!("4600{:03}{:06}", index + 1, i + 1)],
+                    currency_code: "RUB".to_string(),
+                    price: format!("{}.0000", 100 + i * 10),
+                    old_price: format!("{}.0000", 150 + i * 10),
+                    is_archived: i % 10 == 9,
+                    created_at: Some(format_time(created_at)),
+                    updated_at: Some(format_time(created_at + 3600)),
+                },
+                has_fbo_stocks: i % 3 == 0,
+                has_fbs_stocks: i % 2 == 0,
+            }
+        })
+        .collect();
+
+    // 货件每 6 小时一个，最新的一个在一小时前
+    let mut postings: Vec<FakePosting> = (0..i64::from(postings))
+        .map(|i| {
+            let in_process_at = now - 3600 - i * 6 * 3600;
+            let order_id = base_id * 10 + i;
+            let product = products.get(i as usize % products.len().max(1));
+            FakePosting {
+                in_process_at,
+                posting: FbsPosting {
+                    posting_number: format!("{:08}-{:04}-1", order_id % 100_000_000, i % 10_000),
+                    order_id,
+                    order_number: format!("{:08}-{:04}", order_id % 100_000_000, i % 10_000),
+                    status: POSTING_STATUSES[i as usize % POSTING_STATUSES.len()].to_string(),
+                    in_process_at: Some(format_time(in_process_at)),
+                    shipment_date: Some(format_time(in_process_at + 2 * 86_400)),
+                    products: product
+                        .map(|p| FbsPostingProduct {
+                            sku: p.info.id + 500_000,
+                            name: p.info.name.clone(),
+                            offer_id: p.info.offer_id.clone(),
+                            quantity: (i % 3 + 1) as u32,
+                            price: p.info.price.clone(),
+                            currency_code: p.info.currency_code.clone(),
+                        })
+                        .into_iter()
+                        .collect(),
+                },
+            }
+        })
+        .collect();
+    postings.sort_by_key(|p| p.in_process_at);
+
+    Seller { api_key, products, postings }
+}
+
+// 依次模拟延迟、注入故障和校验凭证
+async fn simulate(State(fake): State<FakeOzon>, mut req: Request, next: Next) -> Response {
+    let faults = fake.faults.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let n = fake.counter.fetch_add(1, Ordering::SeqCst) + 1;
+    fake.stats.lock().unwrap_or_else(|e| e.into_inner()).requests += 1;
+
+    if faults.latency_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(faults.latency_ms)).await;
+    }
+
+    if fake.take_failure(&faults, n) {
+        fake.stats.lock().unwrap_or_else(|e| e.into_inner()).injected_failures += 1;
+        let status = StatusCode::from_u16(faults.fail_status)
+            .ok()
+            .filter(|s| s.is_client_error() || s.is_server_error())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = error(status, 13, "injected failure");
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            if let Some(secs) = faults.retry_after {
+                response.headers_mut().insert(header::RETRY_AFTER, secs.into());
+            }
+        }
+        return response;
+    }
+
+    match fake.authenticate(req.headers()) {
+        Ok(client_id) => {
+            req.extensions_mut().insert(SellerId(client_id));
+            next.run(req).await
+        }
+        Err((status, code, message)) => {
+            fake.stats.lock().unwrap_or_else(|e| e.into_inner()).auth_failures += 1;
+            error(status, code, message)
+        }
+    }
+}
+
+impl FakeOzon {
+    fn take_failure(&self, faults: &Faults, n: u64) -> bool {
+        if faults.fail_next > 0 {
+            let mut current = self.faults.lock().unwrap_or_else(|e| e.into_inner());
+            if current.fail_next > 0 {
+                current.fail_next -= 1;
+                return true;
+            }
+        }
+        faults.fail_every > 0 && n.is_multiple_of(faults.fail_every)
+    }
+
+    // 失败时返回 (状态码, Ozon 错误码, 错误信息)
+    fn authenticate(&self, headers: &HeaderMap) -> Result<String, (StatusCode, i64, &'static str)> {
+        let header_value = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+        let (Some(client_id), Some(api_key)) = (header_value(CLIENT_ID_HEADER), header_value(API_KEY_HEADER)) else {
+            return Err((StatusCode::UNAUTHORIZED, 16, "Client-Id and Api-Key headers are required"));
+        };
+
+        match self.sellers.get(client_id) {
+            Some(seller) if seller.api_key == api_key => Ok(client_id.to_string()),
+            _ => Err((StatusCode::FORBIDDEN, 7, "Invalid Api-Key, please contact support")),
+        }
+    }
+
+    fn seller(&self, id: &SellerId) -> &Seller {
+        // 中间件已校验过 client_id
+        &self.sellers[&id.0]
+    }
+}
+
+async fn product_list(
+    State(fake): State<FakeOzon>,
+    Extension(seller_id): Extension<SellerId>,
+    Json(request): Json<ProductListRequest>,
+) -> Response {
+    if request.limit == 0 || request.limit > MAX_LIMIT {
+        return error(StatusCode::BAD_REQUEST, 3, "limit must be between 1 and 1000");
+    }
+    let last_id: i64 = match request.last_id.as_str() {
+        "" => 0,
+        value => match value.parse() {
+            Ok(id) => id,
+            Err(_) => return error(StatusCode::BAD_REQUEST, 3, "invalid last_id"),
+        },
+    };
+
+    let filter = &request.filter;
+    let matched: Vec<&FakeProduct> = fake
+        .seller(&seller_id)
+        .products
+        .iter()
+        .filter(|p| match filter.visibility.as_str() {
+            "ARCHIVED" => p.info.is_archived,
+            "VISIBLE" => !p.info.is_archived,
+            _ => true,
+        })
+        .filter(|p| filter.offer_id.is_empty() || filter.offer_id.contains(&p.info.offer_id))
+        .filter(|p| filter.product_id.is_empty() || filter.product_id.contains(&p.info.id))
+        .collect();
+
+    let items: Vec<ProductListItem> = matched
+        .iter()
+        .filter(|p| p.info.id > last_id)
+        .take(request.limit as usize)
+        .map(|p| ProductListItem {
+            product_id: p.info.id,
+            offer_id: p.info.offer_id.clone(),
+            archived: p.info.is_archived,
+            has_fbo_stocks: p.has_fbo_stocks,
+            has_fbs_stocks: p.has_fbs_stocks,
+        })
+        .collect();
+
+    let last_id = items.last().map(|item| item.product_id.to_string()).unwrap_or_default();
+    Json(ProductListResponse {
+        result: ProductListResult {
+            items,
+            total: matched.len() as u64,
+            last_id,
+        },
+    })
+    .into_response()
+}
+
+async fn product_info_list(
+    State(fake): State<FakeOzon>,
+    Extension(seller_id): Extension<SellerId>,
+    Json(request): Json<ProductInfoListRequest>,
+) -> Response {
+    if request.product_id.len() + request.offer_id.len() > MAX_LIMIT as usize {
+        return error(StatusCode::BAD_REQUEST, 3, "too many identifiers, max 1000");
+    }
+
+    let items = fake
+        .seller(&seller_id)
+        .products
+        .iter()
+        .filter(|p| request.product_id.contains(&p.info.id) || request.offer_id.contains(&p.info.offer_id))
+        .map(|p| p.info.clone())
+        .collect();
+
+    Json(ProductInfoListResponse { items }).into_response()
+}
+
+async fn fbs_posting_list(
+    State(fake): State<FakeOzon>,
+    Extension(seller_id): Extension<SellerId>,
+    Json(request): Json<FbsPostingListRequest>,
+) -> Response {
+    if request.limit == 0 || request.limit > MAX_LIMIT {
+        return error(StatusCode::BAD_REQUEST, 3, "limit must be between 1 and 1000");
+    }
+    let (Some(since), Some(to)) = (parse_time(&request.filter.since), parse_time(&request.filter.to)) else {
+        return error(StatusCode::BAD_REQUEST, 3, "filter.since and filter.to must be RFC 3339 timestamps");
+    };
+
+    let mut matched: Vec<&FakePosting> = fake
+        .seller(&seller_id)
+        .postings
+        .iter()
+        .filter(|p| p.in_process_at >= since && p.in_process_at <= to)
+        .filter(|p| request.filter.status.as_ref().is_none_or(|s| &p.posting.status == s))
+        .collect();
+    if request.dir.eq_ignore_ascii_case("DESC") {
+        matched.reverse();
+    }
+
+    let offset = request.offset as usize;
+    let postings = matched
+        .iter()
+        .skip(offset)
+        .take(request.limit as usize)
+        .map(|p| p.posting.clone())
+        .collect();
+
+    Json(FbsPostingListResponse {
+        result: FbsPostingListResult {
+            postings,
+            has_next: offset + (request.limit as usize) < matched.len(),
+        },
+    })
+    .into_response()
+}
+
+async fn get_faults(State(fake): State<FakeOzon>) -> Json<Faults> {
+    Json(fake.faults.lock().unwrap_or_else(|e| e.into_inner()).clone())
+}
+
+async fn put_faults(State(fake): State<FakeOzon>, Json(faults): Json<Faults>) -> Json<Faults> {
+    *fake.faults.lock().unwrap_or_else(|e| e.into_inner()) = faults.clone();
+    tracing::info!(?faults, "Fake Ozon faults updated");
+    Json(faults)
+}
+
+async fn get_stats(State(fake): State<FakeOzon>) -> Response {
+    let stats = fake.stats.lock().unwrap_or_else(|e| e.into_inner());
+    Json(&*stats).into_response()
+}
+
+fn error(status: StatusCode, code: i64, message: &str) -> Response {
+    let body = ErrorBody {
+        code,
+        message: message.to_string(),
+    };
+    (status, Json(body)).into_response()
+}
+
+fn format_time(ts: i64) -> String {
+    DateTime::<Utc>::from_timestamp(ts, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn parse_time(value: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.timestamp())
+}
@@ -0,0 +1,559 @@
+pub mod fake;
+pub mod models;
+pub mod quota;
+
+use std::sync::Arc;
+use std::time::Duration;
+use axum::body::Bytes;
+use axum::http::{header, HeaderMap, Request, StatusCode};
+use http_body_util::{BodyExt, Full};
+use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
+use hyper_util::client::legacy::{connect::HttpConnector, Client};
+use hyper_util::rt::TokioExecutor;
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+use crate::config::OzonSettings;
+use crate::entities::account;
+use crate::error::AppError;
+use quota::{ApiUsage, RateLimiter, UsageTracker};
+use models::{
+    ErrorBody, FbsPostingListRequest, FbsPostingListResponse, ProductInfoListRequest,
+    ProductInfoListResponse, ProductListRequest, ProductListResponse,
+};
+
+const CLIENT_ID_HEADER: &str = "Client-Id";
+const API_KEY_HEADER: &str = "Api-Key";
+// Retry-After 的上限
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Error)]
+pub enum OzonError {
+    #[error("ozon request failed: {0}")]
+    Transport(String),
+
+    #[error("ozon request timed out")]
+    Timeout,
+
+    // 账号中保存的凭证无法作为请求头发送
+    #[error("invalid ozon request: {0}")]
+    InvalidRequest(String),
+
+    #[error("ozon api error (status {status}, code {code}): {message}")]
+    Api { status: u16, code: i64, message: String },
+
+    // 429，retry_after 来自 Retry-After 响应头
+    #[error("ozon rate limit exceeded: {message}")]
+    RateLimited { retry_after: Option<Duration>, message: String },
+
+    #[error("invalid ozon response: {0}")]
+    Decode(String),
+}
+
+impl OzonError {
+    fn from_response(status: StatusCode, headers: &HeaderMap, body: &[u8]) -> Self {
+        let error: ErrorBody = serde_json::from_slice(body).unwrap_or_default();
+        let message = if error.message.is_empty() {
+            String::from_utf8_lossy(body).chars().take(200).collect()
+        } else {
+            error.message
+        };
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return OzonError::RateLimited {
+                retry_after: retry_after(headers),
+                message,
+            };
+        }
+
+        OzonError::Api {
+            status: status.as_u16(),
+            code: error.code,
+            message,
+        }
+    }
+
+    // 网络错误、超时、限流和服务端错误可以重试；其余错误重试也不会成功
+    fn is_retryable(&self) -> bool {
+        match self {
+            OzonError::Transport(_) | OzonError::Timeout | OzonError::RateLimited { .. } => true,
+            OzonError::Api { status, .. } => *status >= 500,
+            OzonError::InvalidRequest(_) | OzonError::Decode(_) => false,
+        }
+    }
+
+    // Ozon 拒绝了账号凭证（Client-Id / Api-Key 错误或已失效）
+    pub fn is_auth_error(&self) -> bool {
+        matches!(self, OzonError::Api { status: 401 | 403, .. })
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, OzonError::RateLimited { .. })
+    }
+}
+
+// 上游错误对调用方来说是网关错误，而不是调用方自己的 4xx
+impl From<OzonError> for AppError {
+    fn from(err: OzonError) -> Self {
+        tracing::warn!("Ozon error: {}", err);
+        match err {
+            OzonError::Timeout => AppError::GatewayTimeout("ozon request timed out".to_string()),
+            OzonError::Api { status: 401 | 403, message, .. } => {
+                AppError::BadGateway(format!("ozon rejected the account credentials: {}", message))
+            }
+            OzonError::RateLimited { retry_after, .. } => AppError::UpstreamRateLimited {
+                message: "ozon rate limit exceeded".to_string(),
+                retry_after,
+            },
+            OzonError::Api { message, .. } => AppError::BadGateway(format!("ozon error: {}", message)),
+            OzonError::InvalidRequest(message) => AppError::ValidationError(message),
+            OzonError::Transport(_) | OzonError::Decode(_) => {
+                AppError::BadGateway("ozon request failed".to_string())
+            }
+        }
+    }
+}
+
+// Ozon Seller API 客户端：凭证来自每次调用传入的账号；
+// 限流令牌桶和调用统计在所有克隆之间共享
+#[derive(Clone)]
+pub struct OzonClient {
+    http: Client<HttpsConnector<HttpConnector>, Full<Bytes>>,
+    base_url: String,
+    timeout: Duration,
+    verify_timeout: Duration,
+    max_retries: u32,
+    retry_backoff: Duration,
+    max_retry_backoff: Duration,
+    limiter: Arc<RateLimiter>,
+    usage: Arc<UsageTracker>,
+}
+
+impl OzonClient {
+    pub fn new(settings: &OzonSettings) -> std::io::Result<Self> {
+        // 生产访问 Ozon 走 TLS，本地 mock 服务走明文
+        let connector = HttpsConnectorBuilder::new()
+            .with_provider_and_webpki_roots(rustls::crypto::ring::default_provider())
+            .map_err(std::io::Error::other)?
+            .https_or_http()
+            .enable_http1()
+            .build();
+        let http = Client::builder(TokioExecutor::new())
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build(connector);
+
+        Ok(Self {
+            http,
+            base_url: settings.base_url.trim_end_matches('/').to_string(),
+            timeout: Duration::from_secs(settings.timeout),
+            verify_timeout: Duration::from_secs(settings.verify_timeout),
+            max_retries: settings.max_retries,
+            retry_backoff: Duration::from_millis(settings.retry_backoff),
+            max_retry_backoff: Duration::from_millis(settings.max_retry_backoff),
+            limiter: Arc::new(RateLimiter::new(settings.rate_limit, settings.burst)),
+            usage: Arc::new(UsageTracker::default()),
+        })
+    }
+
+    // 供接口内同步等待的凭证校验使用：短超时、不重试，失败直接返回给调用方；
+    // 限流令牌桶和调用统计仍与原客户端共享
+    pub fn for_verification(&self) -> Self {
+        Self {
+            timeout: self.verify_timeout,
+            max_retries: 0,
+            ..self.clone()
+        }
+    }
+
+    // 账号在本进程内的调用统计
+    pub fn usage(&self, account_id: u32) -> ApiUsage {
+        self.usage.get(account_id)
+    }
+
+    pub fn rate_limiter(&self) -> &RateLimiter {
+        &self.limiter
+    }
+
+    pub async fn product_list(
+        &self,
+        account: &account::Model,
+        request: &ProductListRequest,
+    ) -> Result<ProductListResponse, OzonError> {
+        self.post(account, "/v3/product/list", request).await
+    }
+
+    pub async fn product_info_list(
+        &self,
+        account: &account::Model,
+        request: &ProductInfoListRequest,
+    ) -> Result<ProductInfoListResponse, OzonError> {
+        self.post(account, "/v3/product/info/list", request).await
+    }
+
+    pub async fn fbs_posting_list(
+        &self,
+        account: &account::Model,
+        request: &FbsPostingListRequest,
+    ) -> Result<FbsPostingListResponse, OzonError> {
+        self.post(account, "/v3/posting/fbs/list", request).await
+    }
+
+    // Seller API 的方法全部是 POST + JSON
+    pub async fn post<Req, Resp>(
+        &self,
+        account: &account::Model,
+        path: &str,
+        body: &Req,
+    ) -> Result<Resp, OzonError>
+    where
+        Req: Serialize,
+        Resp: DeserializeOwned,
+    {
+        let payload = Bytes::from(
+            serde_json::to_vec(body).map_err(|e| OzonError::InvalidRequest(e.to_string()))?,
+        );
+
+        // 本地排队最多等到重试等待上限或请求超时，更长的等待（通常是 429 后的 Retry-After）
+        // 直接返回给调用方，避免接口请求长时间挂起
+        let max_wait = self.max_retry_backoff.min(self.timeout);
+        let mut attempt = 0;
+        loop {
+            let wait = self
+                .limiter
+                .reserve_within(&account.client_id, max_wait)
+                .map_err(|wait| OzonError::RateLimited {
+                    retry_after: Some(wait),
+                    message: format!("client_id {} is throttled locally", account.client_id),
+                })?;
+            if !wait.is_zero() {
+                self.usage.record_throttle(account.id, wait);
+                tokio::time::sleep(wait).await;
+            }
+
+            let result = self.send(account, path, payload.clone()).await;
+            self.usage.record_call(account.id, result.as_ref().err());
+
+            // 429 时让同一 client_id 的其他请求也暂停，优先采用 Retry-After
+            let mut delay = self.backoff(attempt);
+            if let Err(OzonError::RateLimited { retry_after, .. }) = &result {
+                delay = retry_after.map_or(delay, |after| after.max(delay));
+                self.limiter.penalize(&account.client_id, delay);
+            }
+
+            match result {
+                Ok(body) => {
+                    return serde_json::from_slice(&body).map_err(|e| OzonError::Decode(e.to_string()));
+                }
+                // Retry-After 超过重试等待上限时不在当前请求内等待，直接返回给调用方；
+                // penalize 之后同一 client_id 的请求也会在 reserve_within 处快速失败
+                Err(e) if e.is_retryable() && attempt < self.max_retries && delay <= self.max_retry_backoff => {
+                    tracing::warn!(
+                        account_id = account.id,
+                        path,
+                        attempt = attempt + 1,
+                        "Ozon request failed, retrying in {:?}: {}",
+                        delay,
+                        e
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn send(&self, account: &account::Model, path: &str, payload: Bytes) -> Result<Bytes, OzonError> {
+        let request = Request::post(format!("{}{}", self.base_url, path))
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(CLIENT_ID_HEADER, account.client_id.as_str())
+            .header(API_KEY_HEADER, account.api_key.as_str())
+            .body(Full::new(payload))
+            .map_err(|_| OzonError::InvalidRequest(format!("account {} has invalid credentials", account.id)))?;
+
+        let (status, headers, body) = tokio::time::timeout(self.timeout, async {
+            let response = self
+                .http
+                .request(request)
+                .await
+                .map_err(|e| OzonError::Transport(error_chain(&e)))?;
+            let (parts, body) = response.into_parts();
+            let body = body
+                .collect()
+                .await
+                .map_err(|e| OzonError::Transport(e.to_string()))?
+                .to_bytes();
+            Ok::<_, OzonError>((parts.status, parts.headers, body))
+        })
+        .await
+        .map_err(|_| OzonError::Timeout)??;
+
+        tracing::debug!(account_id = account.id, path, status = status.as_u16(), "Ozon request finished");
+
+        if status.is_success() {
+            Ok(body)
+        } else {
+            Err(OzonError::from_response(status, &headers, &body))
+        }
+    }
+
+    // 指数退避：retry_backoff * 2^attempt，不超过 max_retry_backoff
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_retry_backoff)
+    }
+}
+
+// Retry-After 可以是秒数或 HTTP 日期
+// 超过上限的 Retry-After 按上限处理，避免异常的响应头让请求永久阻塞
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+    let delay = if let Ok(secs) = value.parse::<u64>() {
+        Duration::from_secs(secs)
+    } else {
+        let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+        (at.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()?
+    };
+    Some(delay.min(MAX_RETRY_AFTER))
+}
+
+// hyper 的错误信息很笼统（如 "client error (Connect)"），拼上底层原因便于排查
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+    use super::fake::{self, FakeOzonOptions, Faults};
+    use super::models::{FbsPostingListFilter, ProductListFilter};
+    use super::*;
+
+    // 与 account::Model::fixture(1) 的 client_id 一致
+    const CLIENT_ID: &str = "100001";
+    const API_KEY: &str = "fake-api-key";
+
+    // 在随机端口上启动 fake-ozon，返回 base_url
+    async fn start_fake(products: u32, postings: u32, faults: Faults) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = fake::router(FakeOzonOptions {
+            sellers: vec![(CLIENT_ID.to_string(), API_KEY.to_string())],
+            products,
+            postings,
+            faults,
+        });
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    fn client(base_url: String) -> OzonClient {
+        OzonClient::new(&OzonSettings {
+            base_url,
+            timeout: 5,
+            verify_timeout: 1,
+            max_retries: 3,
+            retry_backoff: 10,
+            max_retry_backoff: 2000,
+            rate_limit: 0.0,
+            burst: 1,
+        })
+        .unwrap()
+    }
+
+    fn account(api_key: &str) -> account::Model {
+        account::Model {
+            api_key: api_key.to_string(),
+            ..account::Model::fixture(1)
+        }
+    }
+
+    fn product_page(last_id: String, limit: u32) -> ProductListRequest {
+        ProductListRequest {
+            filter: ProductListFilter::default(),
+            last_id,
+            limit,
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_until_success() {
+        let faults = Faults { fail_next: 2, fail_status: 503, ..Faults::default() };
+        let ozon = client(start_fake(5, 0, faults).await);
+
+        let response = ozon.product_list(&account(API_KEY), &product_page(String::new(), 10)).await.unwrap();
+
+        assert_eq!(response.result.items.len(), 5);
+        let usage = ozon.usage(1);
+        assert_eq!(usage.calls, 3);
+        assert_eq!(usage.errors, 2);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let faults = Faults { fail_next: 100, fail_status: 500, ..Faults::default() };
+        let ozon = client(start_fake(5, 0, faults).await);
+
+        let err = ozon.product_list(&account(API_KEY), &product_page(String::new(), 10)).await.unwrap_err();
+
+        assert!(matches!(err, OzonError::Api { status: 500, .. }), "{:?}", err);
+        assert_eq!(ozon.usage(1).calls, 4);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_auth_errors() {
+        let ozon = client(start_fake(5, 0, Faults::default()).await);
+
+        let err = ozon.product_list(&account("wrong-key"), &product_page(String::new(), 10)).await.unwrap_err();
+
+        assert!(err.is_auth_error(), "{:?}", err);
+        assert_eq!(ozon.usage(1).calls, 1);
+    }
+
+    #[tokio::test]
+    async fn verification_client_does_not_retry() {
+        let faults = Faults { fail_next: 1, fail_status: 503, ..Faults::default() };
+        let ozon = client(start_fake(5, 0, faults).await);
+
+        let err = ozon
+            .for_verification()
+            .product_list(&account(API_KEY), &product_page(String::new(), 10))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, OzonError::Api { status: 503, .. }), "{:?}", err);
+        assert_eq!(ozon.usage(1).calls, 1);
+    }
+
+    #[tokio::test]
+    async fn verification_client_uses_short_timeout() {
+        let faults = Faults { latency_ms: 3000, ..Faults::default() };
+        let ozon = client(start_fake(5, 0, faults).await);
+
+        let started = Instant::now();
+        let err = ozon
+            .for_verification()
+            .product_list(&account(API_KEY), &product_page(String::new(), 10))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, OzonError::Timeout), "{:?}", err);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn waits_for_retry_after_on_429() {
+        let faults = Faults { fail_next: 1, fail_status: 429, retry_after: Some(1), ..Faults::default() };
+        let ozon = client(start_fake(5, 0, faults).await);
+
+        let started = Instant::now();
+        ozon.product_list(&account(API_KEY), &product_page(String::new(), 10)).await.unwrap();
+
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        let usage = ozon.usage(1);
+        assert_eq!(usage.calls, 2);
+        assert_eq!(usage.rate_limited, 1);
+    }
+
+    #[tokio::test]
+    async fn returns_429_when_retry_after_exceeds_backoff_limit() {
+        let faults = Faults { fail_next: 1, fail_status: 429, retry_after: Some(60), ..Faults::default() };
+        let ozon = client(start_fake(5, 0, faults).await);
+
+        let err = ozon.product_list(&account(API_KEY), &product_page(String::new(), 10)).await.unwrap_err();
+
+        match err {
+            OzonError::RateLimited { retry_after, .. } => assert_eq!(retry_after, Some(Duration::from_secs(60))),
+            err => panic!("expected rate limit error, got {:?}", err),
+        }
+        assert_eq!(ozon.usage(1).calls, 1);
+    }
+
+    #[tokio::test]
+    async fn fails_fast_while_client_id_is_penalized() {
+        let ozon = client(start_fake(5, 0, Faults::default()).await);
+        ozon.rate_limiter().reserve(CLIENT_ID);
+        ozon.rate_limiter().penalize(CLIENT_ID, Duration::from_secs(60));
+
+        let started = Instant::now();
+        let err = ozon
+            .for_verification()
+            .product_list(&account(API_KEY), &product_page(String::new(), 10))
+            .await
+            .unwrap_err();
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        match err {
+            OzonError::RateLimited { retry_after: Some(wait), .. } => assert!(wait > Duration::from_secs(55)),
+            err => panic!("expected rate limit error, got {:?}", err),
+        }
+        assert_eq!(ozon.usage(1).calls, 0);
+    }
+
+    #[tokio::test]
+    async fn pages_through_products_by_last_id() {
+        let ozon = client(start_fake(25, 0, Faults::default()).await);
+        let account = account(API_KEY);
+
+        let mut ids = Vec::new();
+        let mut last_id = String::new();
+        let mut pages = 0;
+        loop {
+            let result = ozon.product_list(&account, &product_page(last_id, 10)).await.unwrap().result;
+            if result.items.is_empty() {
+                break;
+            }
+            assert_eq!(result.total, 25);
+            ids.extend(result.items.iter().map(|item| item.product_id));
+            last_id = result.last_id;
+            pages += 1;
+        }
+
+        assert_eq!(pages, 3);
+        assert_eq!(ids.len(), 25);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[tokio::test]
+    async fn pages_through_postings_by_offset() {
+        let ozon = client(start_fake(5, 25, Faults::default()).await);
+        let account = account(API_KEY);
+        let now = chrono::Utc::now();
+        let since = (now - chrono::Duration::days(30)).to_rfc3339();
+        let to = now.to_rfc3339();
+
+        let mut numbers = Vec::new();
+        let mut offset = 0;
+        loop {
+            let request = FbsPostingListRequest {
+                dir: "ASC".to_string(),
+                filter: FbsPostingListFilter {
+                    since: since.clone(),
+                    to: to.clone(),
+                    status: None,
+                },
+                limit: 10,
+                offset,
+            };
+            let result = ozon.fbs_posting_list(&account, &request).await.unwrap().result;
+            numbers.extend(result.postings.into_iter().map(|p| p.posting_number));
+            if !result.has_next {
+                break;
+            }
+            offset += 10;
+        }
+
+        assert_eq!(offset, 20);
+        assert_eq!(numbers.len(), 25);
+        numbers.sort();
+        numbers.dedup();
+        assert_eq!(numbers.len(), 25);
+    }
+}
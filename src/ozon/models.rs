@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+// Ozon Seller API 的请求/响应结构，只声明本服务用到的字段；
+// 响应字段统一带默认值，Ozon 新增或省略字段时不影响解析。
+// 请求和响应都同时实现序列化与反序列化，本地模拟服务（fake-ozon）复用同一套结构
+
+// 错误响应体，例如 {"code": 7, "message": "Invalid Api-Key", "details": []}
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ErrorBody {
+    #[serde(default)]
+    pub code: i64,
+    #[serde(default)]
+    pub message: String,
+}
+
+// POST /v3/product/list
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProductListRequest {
+    pub filter: ProductListFilter,
+    // 上一页返回的 last_id，首页为空
+    pub last_id: String,
+    pub limit: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProductListFilter {
+    // ALL / VISIBLE / INVISIBLE / ARCHIVED ...
+    pub visibility: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub offer_id: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub product_id: Vec<i64>,
+}
+
+impl Default for ProductListFilter {
+    fn default() -> Self {
+        Self {
+            visibility: "ALL".to_string(),
+            offer_id: Vec::new(),
+            product_id: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProductListResponse {
+    #[serde(default)]
+    pub result: ProductListResult,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProductListResult {
+    #[serde(default)]
+    pub items: Vec<ProductListItem>,
+    #[serde(default)]
+    pub total: u64,
+    #[serde(default)]
+    pub last_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProductListItem {
+    pub product_id: i64,
+    #[serde(default)]
+    pub offer_id: String,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub has_fbo_stocks: bool,
+    #[serde(default)]
+    pub has_fbs_stocks: bool,
+}
+
+// POST /v3/product/info/list
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProductInfoListRequest {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub product_id: Vec<i64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub offer_id: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProductInfoListResponse {
+    #[serde(default)]
+    pub items: Vec<ProductInfo>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProductInfo {
+    pub id: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub offer_id: String,
+    #[serde(default)]
+    pub barcodes: Vec<String>,
+    #[serde(default)]
+    pub currency_code: String,
+    // Ozon 以字符串返回价格，例如 "1299.0000"
+    #[serde(default)]
+    pub price: String,
+    #[serde(default)]
+    pub old_price: String,
+    #[serde(default)]
+    pub is_archived: bool,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+// POST /v3/posting/fbs/list
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FbsPostingListRequest {
+    // ASC / DESC
+    #[serde(default)]
+    pub dir: String,
+    pub filter: FbsPostingListFilter,
+    pub limit: u32,
+    #[serde(default)]
+    pub offset: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FbsPostingListFilter {
+    // RFC 3339 时间，按 in_process_at 过滤
+    pub since: String,
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FbsPostingListResponse {
+    #[serde(default)]
+    pub result: FbsPostingListResult,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FbsPostingListResult {
+    #[serde(default)]
+    pub postings: Vec<FbsPosting>,
+    #[serde(default)]
+    pub has_next: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FbsPosting {
+    pub posting_number: String,
+    #[serde(default)]
+    pub order_id: i64,
+    #[serde(default)]
+    pub order_number: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub in_process_at: Option<String>,
+    #[serde(default)]
+    pub shipment_date: Option<String>,
+    #[serde(default)]
+    pub products: Vec<FbsPostingProduct>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FbsPostingProduct {
+    #[serde(default)]
+    pub sku: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub offer_id: String,
+    #[serde(default)]
+    pub quantity: u32,
+    #[serde(default)]
+    pub price: String,
+    #[serde(default)]
+    pub currency_code: String,
+}
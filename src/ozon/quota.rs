@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+
+// 按 client_id 的令牌桶。Ozon 按 client_id 限流，同一 client_id 的所有请求
+// （包括不同账号记录、不同后台任务）共享一个桶
+pub struct RateLimiter {
+    // 每秒补充的令牌数，0 表示不限流
+    rate: f64,
+    burst: f64,
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+struct Bucket {
+    // 可以为负数：表示已经预约出去、需要排队等待的请求
+    tokens: f64,
+    updated_at: Instant,
+    // 收到 429 后在该时间之前不再发出请求
+    blocked_until: Option<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(rate: f64, burst: u32) -> Self {
+        Self {
+            rate,
+            burst: f64::from(burst.max(1)),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.burst as u32
+    }
+
+    // 预约一个令牌，返回发出请求前需要等待的时间
+    pub fn reserve(&self, client_id: &str) -> Duration {
+        self.reserve_within(client_id, Duration::MAX).unwrap_or_else(|wait| wait)
+    }
+
+    // 同 reserve，但需要等待超过 max_wait 时归还令牌，返回 Err(需要等待的时间)
+    pub fn reserve_within(&self, client_id: &str, max_wait: Duration) -> Result<Duration, Duration> {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = buckets.entry(client_id.to_string()).or_insert(Bucket {
+            tokens: self.burst,
+            updated_at: now,
+            blocked_until: None,
+        });
+
+        let blocked = bucket
+            .blocked_until
+            .and_then(|until| until.checked_duration_since(now))
+            .unwrap_or_default();
+        if blocked.is_zero() {
+            bucket.blocked_until = None;
+        }
+
+        if self.rate <= 0.0 {
+            return if blocked > max_wait { Err(blocked) } else { Ok(blocked) };
+        }
+
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst) - 1.0;
+        bucket.updated_at = now;
+
+        let wait = if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        };
+        let wait = wait.max(blocked);
+        if wait > max_wait {
+            bucket.tokens += 1.0;
+            return Err(wait);
+        }
+        Ok(wait)
+    }
+
+    // Ozon 返回 429：同一 client_id 的后续请求至少等待 delay，并清空已积累的令牌
+    pub fn penalize(&self, client_id: &str, delay: Duration) {
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(bucket) = buckets.get_mut(client_id) {
+            let Some(until) = now.checked_add(delay) else { return };
+            bucket.blocked_until = Some(bucket.blocked_until.map_or(until, |current| current.max(until)));
+            bucket.tokens = bucket.tokens.min(0.0);
+        }
+    }
+}
+
+// 单个账号的调用统计（进程内，自启动以来累计）
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ApiUsage {
+    // 实际发出的 HTTP 请求数（含重试）
+    pub calls: u64,
+    pub errors: u64,
+    // 其中被 Ozon 限流（429）的次数
+    pub rate_limited: u64,
+    // 因本地令牌桶或 429 等待的次数及累计时长
+    pub throttled: u64,
+    pub throttled_ms: u64,
+    pub last_call_at: Option<u32>,
+    pub last_error_at: Option<u32>,
+    pub last_error: Option<String>,
+}
+
+impl ApiUsage {
+    pub fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.calls as f64
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct UsageTracker {
+    accounts: Mutex<HashMap<u32, ApiUsage>>,
+}
+
+impl UsageTracker {
+    pub fn get(&self, account_id: u32) -> ApiUsage {
+        let accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+        accounts.get(&account_id).cloned().unwrap_or_default()
+    }
+
+    pub fn record_throttle(&self, account_id: u32, wait: Duration) {
+        self.update(account_id, |usage| {
+            usage.throttled += 1;
+            usage.throttled_ms += wait.as_millis() as u64;
+        });
+    }
+
+    pub fn record_call(&self, account_id: u32, error: Option<&super::OzonError>) {
+        let now = chrono::Utc::now().timestamp() as u32;
+        self.update(account_id, |usage| {
+            usage.calls += 1;
+            usage.last_call_at = Some(now);
+            if let Some(error) = error {
+                usage.errors += 1;
+                usage.last_error_at = Some(now);
+                usage.last_error = Some(error.to_string().chars().take(200).collect());
+                if error.is_rate_limited() {
+                    usage.rate_limited += 1;
+                }
+            }
+        });
+    }
+
+    fn update(&self, account_id: u32, f: impl FnOnce(&mut ApiUsage)) {
+        let mut accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+        f(accounts.entry(account_id).or_default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reserve 内部读取真实时钟，两次调用之间会流逝少量时间
+    fn assert_about(actual: Duration, expected_ms: u64) {
+        let expected = Duration::from_millis(expected_ms);
+        assert!(
+            actual <= expected && expected - actual < Duration::from_millis(20),
+            "expected about {:?}, got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn burst_passes_then_queues_at_rate() {
+        let limiter = RateLimiter::new(10.0, 2);
+
+        assert_eq!(limiter.reserve("a"), Duration::ZERO);
+        assert_eq!(limiter.reserve("a"), Duration::ZERO);
+        assert_about(limiter.reserve("a"), 100);
+        assert_about(limiter.reserve("a"), 200);
+    }
+
+    #[test]
+    fn buckets_are_per_client_id() {
+        let limiter = RateLimiter::new(1.0, 1);
+
+        assert_eq!(limiter.reserve("a"), Duration::ZERO);
+        assert_about(limiter.reserve("a"), 1000);
+        assert_eq!(limiter.reserve("b"), Duration::ZERO);
+    }
+
+    #[test]
+    fn reserve_within_returns_token_when_wait_is_too_long() {
+        let limiter = RateLimiter::new(10.0, 1);
+        assert_eq!(limiter.reserve("a"), Duration::ZERO);
+
+        let wait = limiter.reserve_within("a", Duration::from_millis(50)).unwrap_err();
+        assert_about(wait, 100);
+        // 被拒绝的预约不占用令牌，下一个请求仍然只需排一个位置
+        assert_about(limiter.reserve_within("a", Duration::from_millis(150)).unwrap(), 100);
+    }
+
+    #[test]
+    fn reserve_within_rejects_long_penalty() {
+        let limiter = RateLimiter::new(0.0, 1);
+        assert_eq!(limiter.reserve("a"), Duration::ZERO);
+
+        limiter.penalize("a", Duration::from_secs(3600));
+        let wait = limiter.reserve_within("a", Duration::from_secs(10)).unwrap_err();
+        assert!(wait > Duration::from_secs(3590), "{:?}", wait);
+    }
+
+    #[test]
+    fn zero_rate_never_waits() {
+        let limiter = RateLimiter::new(0.0, 1);
+
+        for _ in 0..100 {
+            assert_eq!(limiter.reserve("a"), Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn penalize_blocks_and_drops_saved_tokens() {
+        let limiter = RateLimiter::new(10.0, 5);
+        assert_eq!(limiter.reserve("a"), Duration::ZERO);
+
+        limiter.penalize("a", Duration::from_millis(500));
+        assert_about(limiter.reserve("a"), 500);
+        // 令牌已清空，等待时间不短于按速率排队的时间
+        assert!(limiter.reserve("a") >= Duration::from_millis(190));
+    }
+
+    #[test]
+    fn penalize_ignores_unknown_client_and_overflow() {
+        let limiter = RateLimiter::new(0.0, 1);
+
+        limiter.penalize("a", Duration::from_secs(60));
+        assert_eq!(limiter.reserve("a"), Duration::ZERO);
+
+        limiter.penalize("a", Duration::MAX);
+        assert_eq!(limiter.reserve("a"), Duration::ZERO);
+    }
+}
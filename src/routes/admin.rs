@@ -0,0 +1,25 @@
+use axum::{routing::get, Router};
+use crate::{
+    controllers::{
+        admin_controller::{config_dump, get_log_level, metrics, set_log_level},
+        health_controller::{health_check, liveness, readiness},
+    },
+    state::AppState,
+};
+
+// 健康检查和指标端点：未配置管理端口时挂在公共路由上
+pub fn monitoring_routes() -> Router<AppState> {
+    Router::new()
+        .route("/health", get(health_check))
+        .route("/health/live", get(liveness))
+        .route("/health/ready", get(readiness))
+        .route("/metrics", get(metrics))
+}
+
+// 管理端点：只在独立的管理监听器上提供
+pub fn admin_routes() -> Router<AppState> {
+    Router::new()
+        .merge(monitoring_routes())
+        .route("/admin/config", get(config_dump))
+        .route("/admin/log-level", get(get_log_level).put(set_log_level))
+}
@@ -1,23 +1,35 @@
 use axum::{
-    routing::{get, post},
+    routing::{get, patch, post, put},
     Router,
 };
 use crate::{
+    auth::permissions::{
+        ACCOUNTS_ASSIGN, ACCOUNTS_CREDENTIALS, ACCOUNTS_DELETED, ACCOUNTS_READ, ACCOUNTS_WRITE, AUDIT_READ,
+    },
     controllers::{
-        fibonacci::{fibonacci_controller, health_check},
+        fibonacci::fibonacci_controller,
         account_controller::{
             list_all_accounts, list_enabled_accounts, list_disabled_accounts, get_accounts_summary,
+            get_account, get_account_credentials, enable_account, disable_account,
+            reassign_account_owner, get_account_audit, update_account, bulk_update_accounts,
+            export_accounts, import_accounts, verify_account, get_account_api_usage,
+            list_expiring_accounts, find_duplicate_accounts, stage_pending_key, cancel_pending_key, verify_pending_key,
+            promote_pending_key, rollback_key, delete_account, restore_account,
         },
+        audit_controller::search_audit_logs,
+        posting_controller::{list_account_postings, sync_account_postings},
+        product_controller::{list_account_products, sync_account_products},
     },
+    middleware::auth::require,
     state::AppState,
 };
 
 pub fn api_routes() -> Router<AppState> {
     Router::new()
         .nest("/api", api_internal_routes())
-        .route("/health", get(health_check))
         // 新增账号管理端点
         .nest("/accounts", account_routes())
+        .nest("/audit", audit_routes())
 }
 
 fn api_internal_routes() -> Router<AppState> {
@@ -39,8 +51,38 @@ fn fibonacci_routes() -> Router<AppState> {
 
 fn account_routes() -> Router<AppState> {
     Router::new()
-        .route("/", get(list_all_accounts))                    // 获取所有账号
-        .route("/enabled", get(list_enabled_accounts))        // 获取启用的账号
-        .route("/disabled", get(list_disabled_accounts))       // 获取未启用的账号
-        .route("/summary", get(get_accounts_summary))           // 获取统计信息
+        .route("/", require(ACCOUNTS_READ, get(list_all_accounts)))                   // 获取所有账号
+        .route("/enabled", require(ACCOUNTS_READ, get(list_enabled_accounts)))       // 获取启用的账号
+        .route("/disabled", require(ACCOUNTS_READ, get(list_disabled_accounts)))      // 获取未启用的账号
+        .route("/summary", require(ACCOUNTS_READ, get(get_accounts_summary)))          // 获取统计信息
+        .route("/bulk", require(ACCOUNTS_WRITE, post(bulk_update_accounts)))          // 批量操作
+        .route("/export", require(ACCOUNTS_READ, get(export_accounts)))              // 导出账号
+        .route("/import", require(ACCOUNTS_WRITE, post(import_accounts)))            // 导入账号
+        .route("/expiring", require(ACCOUNTS_READ, get(list_expiring_accounts)))     // api_key 即将过期的账号
+        .route("/duplicates", require(ACCOUNTS_READ, get(find_duplicate_accounts)))  // 重复账号报告
+        .route("/{id}", require(ACCOUNTS_READ, get(get_account))                      // 获取单个账号
+            .merge(require(ACCOUNTS_WRITE, patch(update_account)                    // 修改账号
+                .delete(delete_account))))                                           // 删除（归档）账号
+        .route("/{id}/restore", require(ACCOUNTS_DELETED, post(restore_account)))   // 恢复已删除的账号
+        .route("/{id}/credentials", require(ACCOUNTS_CREDENTIALS, get(get_account_credentials))) // 查看凭证
+        .route("/{id}/credentials/pending", require(ACCOUNTS_WRITE, put(stage_pending_key) // 暂存新 key
+            .delete(cancel_pending_key)))                                            // 丢弃新 key
+        .route("/{id}/credentials/pending/verify", require(ACCOUNTS_WRITE, post(verify_pending_key))) // 校验新 key
+        .route("/{id}/credentials/promote", require(ACCOUNTS_WRITE, post(promote_pending_key))) // 启用新 key
+        .route("/{id}/credentials/rollback", require(ACCOUNTS_WRITE, post(rollback_key))) // 恢复旧 key
+        .route("/{id}/enable", require(ACCOUNTS_WRITE, post(enable_account)))         // 启用账号
+        .route("/{id}/disable", require(ACCOUNTS_WRITE, post(disable_account)))       // 禁用账号
+        .route("/{id}/owner", require(ACCOUNTS_ASSIGN, put(reassign_account_owner)))  // 调整负责人
+        .route("/{id}/verify", require(ACCOUNTS_WRITE, post(verify_account)))         // 校验凭证
+        .route("/{id}/api-usage", require(ACCOUNTS_READ, get(get_account_api_usage))) // Ozon API 调用统计
+        .route("/{id}/audit", require(ACCOUNTS_READ, get(get_account_audit)))        // 账号审计日志
+        .route("/{id}/products", require(ACCOUNTS_READ, get(list_account_products)))  // 本地同步的商品
+        .route("/{id}/products/sync", require(ACCOUNTS_WRITE, post(sync_account_products))) // 立即同步商品
+        .route("/{id}/postings", require(ACCOUNTS_READ, get(list_account_postings)))  // 本地同步的 FBS 货件
+        .route("/{id}/postings/sync", require(ACCOUNTS_WRITE, post(sync_account_postings))) // 立即同步货件
+}
+
+fn audit_routes() -> Router<AppState> {
+    Router::new()
+        .route("/", require(AUDIT_READ, get(search_audit_logs)))                     // 审计日志查询
 }
\ No newline at end of file
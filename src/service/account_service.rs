@@ -1,125 +1,234 @@
-use sea_orm::{DatabaseConnection, EntityTrait, QueryFilter, ColumnTrait, QuerySelect, QueryOrder, PaginatorTrait};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use futures::{Stream, TryStreamExt};
+use sea_orm::sea_query::{Expr, Func, SelectStatement, SimpleExpr};
+use sea_orm::{
+    ActiveValue::{NotSet, Set}, ConnectionTrait, DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait,
+    QueryFilter, ColumnTrait, QuerySelect, QueryOrder, QueryTrait, PaginatorTrait, Select, SqlErr, TransactionTrait,
+};
+use crate::auth::AccountScope;
+use crate::cache::AccountCache;
 use crate::entities::account;
-use crate::models::account::{AccountResponse, AccountsListResponse, AccountSummaryResponse};
+use crate::etag::CollectionVersion;
+use crate::service::audit_service::{actions, AuditContext, AuditService};
+use crate::service::rotation_service::RotationService;
+use crate::service::verification_service::status as verify_status;
+use crate::models::import::{
+    ImportParams, ImportResponse, ImportRow, ImportRowResult, ImportRowStatus, ParsedRow,
+};
+use crate::models::account::{
+    AccountResponse, AccountsListResponse, AccountSummaryResponse, BulkAction, CredentialStatusSummary, BulkItemResult,
+    BulkItemStatus, BulkMode, BulkOperation, BulkRequest, BulkResponse, DuplicateAccount, DuplicateField,
+    DuplicateGroup, DuplicatesResponse, UpdateAccountRequest,
+};
+
+// 导入单行的结果：状态、账号 ID、附加说明
+type ImportOutcome = (ImportRowStatus, Option<u32>, Option<String>);
+
+// 单个按条件批量操作最多影响的账号数
+const MAX_BULK_ITEMS: u64 = 1000;
+
+// 导出明文凭证时，每批账号先写审计再输出
+const EXPORT_AUDIT_BATCH: usize = 500;
+
+// 带版本校验的修改结果
+pub enum UpdateResult {
+    Updated(account::Model),
+    NotFound,
+    // 当前版本与 If-Match 不一致，携带最新数据用于返回当前 ETag
+    VersionMismatch(account::Model),
+    // client_id 或账号名称已被其他账号使用
+    Conflict(String),
+}
 
 // Service 改为无状态（空结构体）
 pub struct AccountService;
 
 impl AccountService {
+    // 所有账号查询的入口，按调用方的可见范围过滤，避免任何接口绕过负责人隔离；
+    // 已删除（归档）的账号对所有查询不可见
+    fn find_scoped(scope: &AccountScope) -> Select<account::Entity> {
+        Self::find_scoped_with_deleted(scope, false)
+    }
+
+    // 调用方可见账号的 id 子查询，供审计日志等按账号关联的表过滤；
+    // AccountScope::All 且包含已删除账号时不需要过滤，返回 None
+    pub fn visible_ids(scope: &AccountScope, include_deleted: bool) -> Option<SelectStatement> {
+        if matches!(scope, AccountScope::All) && include_deleted {
+            return None;
+        }
+        Some(
+            Self::find_scoped_with_deleted(scope, include_deleted)
+                .select_only()
+                .column(account::Column::Id)
+                .into_query(),
+        )
+    }
+
+    // 同 find_scoped，include_deleted 时包含已删除的账号（管理员查看和恢复）
+    fn find_scoped_with_deleted(scope: &AccountScope, include_deleted: bool) -> Select<account::Entity> {
+        let query = match scope {
+            AccountScope::All => account::Entity::find(),
+            AccountScope::Owners(owners) => account::Entity::find()
+                .filter(account::Column::UserName.is_in(owners.iter().cloned())),
+        };
+        if include_deleted {
+            query
+        } else {
+            query.filter(account::Column::DeletedAt.is_null())
+        }
+    }
+
     // 获取所有账号（带分页）
     pub async fn get_all_accounts(
         db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        include_deleted: bool,
         page: u64,
         page_size: u64,
     ) -> Result<AccountsListResponse, sea_orm::DbErr> {
-        let offset = (page - 1) * page_size;
-
-        let total = account::Entity::find()
-            .count(db)
-            .await?;
-
-        let accounts = account::Entity::find()
-            .order_by_asc(account::Column::Id)
-            .offset(offset)
-            .limit(page_size)
-            .all(db)
-            .await?;
-
-        let total_pages = (total + page_size - 1) / page_size;
-
-        let response_data: Vec<AccountResponse> = accounts
-            .into_iter()
-            .map(AccountResponse::from)
-            .collect();
-
-        Ok(AccountsListResponse {
-            total,
-            data: response_data,
-            page,
-            page_size,
-            total_pages,
-        })
+        Self::list_accounts(db, cache, scope, None, include_deleted, page, page_size).await
     }
 
     // 获取启用的账号
     pub async fn get_enabled_accounts(
         db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        include_deleted: bool,
         page: u64,
         page_size: u64,
     ) -> Result<AccountsListResponse, sea_orm::DbErr> {
-        let offset = (page - 1) * page_size;
-
-        let total = account::Entity::find()
-            .filter(account::Column::IsEnable.eq(1))
-            .count(db)
-            .await?;
-
-        let accounts = account::Entity::find()
-            .filter(account::Column::IsEnable.eq(1))
-            .order_by_asc(account::Column::Id)
-            .offset(offset)
-            .limit(page_size)
-            .all(db)
-            .await?;
-
-        let total_pages = (total + page_size - 1) / page_size;
-
-        let response_data: Vec<AccountResponse> = accounts
-            .into_iter()
-            .map(AccountResponse::from)
-            .collect();
-
-        Ok(AccountsListResponse {
-            total,
-            data: response_data,
-            page,
-            page_size,
-            total_pages,
-        })
+        Self::list_accounts(db, cache, scope, Some(1), include_deleted, page, page_size).await
     }
 
     // 获取禁用的账号
     pub async fn get_disabled_accounts(
         db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        include_deleted: bool,
         page: u64,
         page_size: u64,
     ) -> Result<AccountsListResponse, sea_orm::DbErr> {
-        let offset = (page - 1) * page_size;
+        Self::list_accounts(db, cache, scope, Some(0), include_deleted, page, page_size).await
+    }
 
-        let total = account::Entity::find()
-            .filter(account::Column::IsEnable.eq(0))
-            .count(db)
-            .await?;
+    // 分页查询（可按启用状态过滤），结果经过进程内缓存
+    async fn list_accounts(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        is_enable: Option<u8>,
+        include_deleted: bool,
+        page: u64,
+        page_size: u64,
+    ) -> Result<AccountsListResponse, sea_orm::DbErr> {
+        let key = format!("{:?}|{:?}|{}|{}|{}", scope, is_enable, include_deleted, page, page_size);
 
-        let accounts = account::Entity::find()
-            .filter(account::Column::IsEnable.eq(0))
-            .order_by_asc(account::Column::Id)
-            .offset(offset)
-            .limit(page_size)
-            .all(db)
-            .await?;
+        cache.list(key, async {
+            let offset = (page - 1) * page_size;
 
-        let total_pages = (total + page_size - 1) / page_size;
+            let mut query = Self::find_scoped_with_deleted(scope, include_deleted);
+            if let Some(is_enable) = is_enable {
+                query = query.filter(account::Column::IsEnable.eq(is_enable));
+            }
 
-        let response_data: Vec<AccountResponse> = accounts
-            .into_iter()
-            .map(AccountResponse::from)
-            .collect();
+            let total = query
+                .clone()
+                .count(db)
+                .await?;
 
-        Ok(AccountsListResponse {
-            total,
-            data: response_data,
-            page,
-            page_size,
-            total_pages,
+            let accounts = query
+                .order_by_asc(account::Column::Id)
+                .offset(offset)
+                .limit(page_size)
+                .all(db)
+                .await?;
+
+            let total_pages = total.div_ceil(page_size);
+
+            let response_data: Vec<AccountResponse> = accounts
+                .into_iter()
+                .map(AccountResponse::from)
+                .collect();
+
+            Ok(AccountsListResponse {
+                total,
+                data: response_data,
+                page,
+                page_size,
+                total_pages,
+            })
         })
+        .await
+    }
+
+    // 导出账号：逐行从数据库流式读取，不把结果集整体加载到内存
+    // audit 不为空时（导出明文 api_key），每个账号在输出前写一条 credentials_exported 审计记录，
+    // 审计写入失败则中断导出
+    pub fn export_accounts(
+        db: DatabaseConnection,
+        scope: AccountScope,
+        is_enable: Option<u8>,
+        include_deleted: bool,
+        audit: Option<AuditContext>,
+    ) -> impl Stream<Item = Result<account::Model, sea_orm::DbErr>> + Send + 'static {
+        async_stream::try_stream! {
+            let mut query = Self::find_scoped_with_deleted(&scope, include_deleted);
+            if let Some(is_enable) = is_enable {
+                query = query.filter(account::Column::IsEnable.eq(is_enable));
+            }
+
+            let mut rows = query
+                .order_by_asc(account::Column::Id)
+                .stream(&db)
+                .await?;
+
+            let mut batch = Vec::with_capacity(EXPORT_AUDIT_BATCH);
+            while let Some(model) = rows.try_next().await? {
+                batch.push(model);
+                if batch.len() >= EXPORT_AUDIT_BATCH {
+                    Self::audit_export(&db, audit.as_ref(), &batch).await?;
+                    for model in batch.drain(..) {
+                        yield model;
+                    }
+                }
+            }
+
+            Self::audit_export(&db, audit.as_ref(), &batch).await?;
+            for model in batch {
+                yield model;
+            }
+        }
+    }
+
+    async fn audit_export(
+        db: &DatabaseConnection,
+        audit: Option<&AuditContext>,
+        batch: &[account::Model],
+    ) -> Result<(), sea_orm::DbErr> {
+        let Some(ctx) = audit else {
+            return Ok(());
+        };
+        let ids: Vec<u32> = batch.iter().map(|model| model.id).collect();
+        AuditService::record_many(db, ctx, &ids, actions::CREDENTIALS_EXPORTED).await
     }
 
     // 获取账号统计
     pub async fn get_accounts_summary(
         db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+    ) -> Result<AccountSummaryResponse, sea_orm::DbErr> {
+        cache.summary(format!("{:?}", scope), Self::load_accounts_summary(db, scope)).await
+    }
+
+    async fn load_accounts_summary(
+        db: &DatabaseConnection,
+        scope: &AccountScope,
     ) -> Result<AccountSummaryResponse, sea_orm::DbErr> {
-        let all_accounts = account::Entity::find()
+        let all_accounts = Self::find_scoped(scope)
             .all(db)
             .await?;
 
@@ -135,6 +244,16 @@ impl AccountService {
             .filter(|account| account.is_enable == 0)
             .count() as u64;
 
+        let mut credentials = CredentialStatusSummary::default();
+        for account in &all_accounts {
+            match account.verify_status.as_str() {
+                verify_status::OK => credentials.ok += 1,
+                verify_status::INVALID => credentials.invalid += 1,
+                verify_status::ERROR => credentials.error += 1,
+                _ => credentials.unknown += 1,
+            }
+        }
+
         let companies: Vec<String> = all_accounts
             .into_iter()
             .map(|account| account.company_name)
@@ -148,6 +267,925 @@ impl AccountService {
             enabled_accounts,
             disabled_accounts,
             companies,
+            credentials,
         })
     }
+
+    // 按启用状态统计账号数量，返回 (启用数, 禁用数)
+    pub async fn count_accounts_by_status(
+        db: &DatabaseConnection,
+        scope: &AccountScope,
+    ) -> Result<(u64, u64), sea_orm::DbErr> {
+        let enabled = Self::find_scoped(scope)
+            .filter(account::Column::IsEnable.eq(1))
+            .count(db)
+            .await?;
+
+        let disabled = Self::find_scoped(scope)
+            .filter(account::Column::IsEnable.eq(0))
+            .count(db)
+            .await?;
+
+        Ok((enabled, disabled))
+    }
+
+    // 计算列表/统计数据的版本（行数 + 最大 update_time），用于条件请求
+    pub async fn collection_version(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        is_enable: Option<u8>,
+        include_deleted: bool,
+    ) -> Result<CollectionVersion, sea_orm::DbErr> {
+        let key = format!("{:?}|{:?}|{}", scope, is_enable, include_deleted);
+
+        cache.version(key, async {
+            let mut query = Self::find_scoped_with_deleted(scope, include_deleted);
+            if let Some(is_enable) = is_enable {
+                query = query.filter(account::Column::IsEnable.eq(is_enable));
+            }
+
+            // 凭证校验不修改 update_time，但会改变列表内容，也计入版本。
+            // 旧 key 宽限期结束时 previous_key_valid_until 在响应中变为 null，同样不修改
+            // update_time，因此把已到期的宽限期截止时间当作一次修改计入；清理任务随后删除
+            // 该值时响应内容不再变化
+            let now = chrono::Utc::now().timestamp() as u32;
+            let grace_ended = SimpleExpr::from(Func::max(Expr::case(
+                account::Column::PreviousKeyValidUntil.lte(now),
+                Expr::col(account::Column::PreviousKeyValidUntil),
+            )));
+            let (count, last_modified, last_verified, last_grace_ended) = query
+                .select_only()
+                .column_as(account::Column::Id.count(), "count")
+                .column_as(account::Column::UpdateTime.max(), "last_modified")
+                .column_as(account::Column::LastVerifiedAt.max(), "last_verified")
+                .column_as(grace_ended, "last_grace_ended")
+                .into_tuple::<(i64, Option<u32>, Option<u32>, Option<u32>)>()
+                .one(db)
+                .await?
+                .unwrap_or_default();
+
+            Ok(CollectionVersion {
+                count: count as u64,
+                last_modified: last_modified.max(last_verified).max(last_grace_ended).unwrap_or(0),
+            })
+        })
+        .await
+    }
+
+    // 按 ID 获取账号
+    pub async fn get_account_by_id(
+        db: &DatabaseConnection,
+        scope: &AccountScope,
+        id: u32,
+    ) -> Result<Option<account::Model>, sea_orm::DbErr> {
+        Self::get_account_with_deleted(db, scope, id, false).await
+    }
+
+    // 按 ID 获取账号，include_deleted 时包含已删除的账号
+    pub async fn get_account_with_deleted(
+        db: &DatabaseConnection,
+        scope: &AccountScope,
+        id: u32,
+        include_deleted: bool,
+    ) -> Result<Option<account::Model>, sea_orm::DbErr> {
+        Self::find_scoped_with_deleted(scope, include_deleted)
+            .filter(account::Column::Id.eq(id))
+            .one(db)
+            .await
+    }
+
+    // 软删除账号：设置 deleted_at，之后所有查询和后台任务都不再包含该账号，
+    // 保留期内可以恢复
+    pub async fn delete_account(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+    ) -> Result<UpdateResult, sea_orm::DbErr> {
+        let now = chrono::Utc::now().timestamp() as u32;
+
+        Self::update_with_audit(db, scope, ctx, id, expected_version, actions::DELETE, |active| {
+            active.deleted_at = Set(Some(now));
+        })
+        .await
+        .map(|result| Self::invalidate_if_updated(cache, result))
+    }
+
+    // 恢复已删除的账号。唯一索引包含已删除的账号，恢复时不会产生 client_id/名称冲突
+    pub async fn restore_account(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+    ) -> Result<UpdateResult, sea_orm::DbErr> {
+        let Some(before) = Self::get_account_with_deleted(db, scope, id, true).await? else {
+            return Ok(UpdateResult::NotFound);
+        };
+        if before.deleted_at.is_none() {
+            return Ok(UpdateResult::Conflict(format!("account {} is not deleted", id)));
+        }
+
+        let query = Self::find_scoped_with_deleted(scope, true).filter(account::Column::DeletedAt.is_not_null());
+        Self::update_matching(db, query, ctx, id, expected_version, actions::RESTORE, |active| {
+            active.deleted_at = Set(None);
+        })
+        .await
+        .map(|result| Self::invalidate_if_updated(cache, result))
+    }
+
+    // 启用/禁用账号
+    pub async fn set_account_enabled(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+        enabled: bool,
+    ) -> Result<UpdateResult, sea_orm::DbErr> {
+        let action = if enabled { actions::ENABLE } else { actions::DISABLE };
+
+        Self::update_with_audit(db, scope, ctx, id, expected_version, action, |active| {
+            active.is_enable = Set(u8::from(enabled));
+        })
+        .await
+        .map(|result| Self::invalidate_if_updated(cache, result))
+    }
+
+    // 调整账号负责人
+    pub async fn reassign_owner(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+        user_name: &str,
+    ) -> Result<UpdateResult, sea_orm::DbErr> {
+        Self::update_with_audit(db, scope, ctx, id, expected_version, actions::REASSIGN_OWNER, |active| {
+            active.user_name = Set(user_name.to_string());
+        })
+        .await
+        .map(|result| Self::invalidate_if_updated(cache, result))
+    }
+
+    // 修改账号基础信息，只更新请求中出现的字段
+    pub async fn update_account(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+        request: &UpdateAccountRequest,
+    ) -> Result<UpdateResult, sea_orm::DbErr> {
+        // 先确认账号可见，避免通过冲突信息探测其他负责人的账号
+        if Self::get_account_by_id(db, scope, id).await?.is_none() {
+            return Ok(UpdateResult::NotFound);
+        }
+        let conflict =
+            Self::find_conflict(db, request.client_id.as_deref(), request.account.as_deref(), Some(id)).await?;
+        if let Some(message) = conflict {
+            return Ok(UpdateResult::Conflict(message));
+        }
+
+        let result = Self::update_with_audit(db, scope, ctx, id, expected_version, actions::UPDATE, |active| {
+            if let Some(name) = &request.account {
+                active.account = Set(name.clone());
+            }
+            if let Some(client_id) = &request.client_id {
+                active.client_id = Set(client_id.clone());
+            }
+            if let Some(currency_code) = &request.currency_code {
+                active.currency_code = Set(currency_code.clone());
+            }
+            if let Some(company_name) = &request.company_name {
+                active.company_name = Set(company_name.clone());
+            }
+            if let Some(data) = &request.data {
+                active.data = Set(data.clone());
+            }
+            if let Some(key_expires_at) = request.key_expires_at {
+                active.key_expires_at = Set(Some(key_expires_at));
+            }
+        })
+        .await;
+
+        // 并发写入时由唯一索引兜底
+        match result {
+            Err(e) if Self::is_unique_violation(&e) => Ok(UpdateResult::Conflict(
+                "client_id or account name is already used by another account".to_string(),
+            )),
+            result => result.map(|result| Self::invalidate_if_updated(cache, result)),
+        }
+    }
+
+    // client_id 和账号名称在全表内唯一（不按可见范围过滤），返回冲突说明
+    pub async fn find_conflict<C: ConnectionTrait>(
+        conn: &C,
+        client_id: Option<&str>,
+        account_name: Option<&str>,
+        exclude_id: Option<u32>,
+    ) -> Result<Option<String>, DbErr> {
+        let checks = [
+            (account::Column::ClientId, client_id, "client_id"),
+            (account::Column::Account, account_name, "account name"),
+        ];
+
+        for (column, value, label) in checks {
+            let Some(value) = value else { continue };
+            let mut query = account::Entity::find().filter(column.eq(value));
+            if let Some(id) = exclude_id {
+                query = query.filter(account::Column::Id.ne(id));
+            }
+            // 已删除的账号在彻底清理前仍占用 client_id 和名称
+            if let Some(existing) = query.one(conn).await? {
+                let owner = if existing.deleted_at.is_some() { "a deleted account" } else { "another account" };
+                return Ok(Some(format!("{} {:?} is already used by {}", label, value, owner)));
+            }
+        }
+        Ok(None)
+    }
+
+    // 按 client_id、账号名称、公司名称查找可疑的重复账号（只在调用方可见范围内查找）。
+    // 名称类字段归一化后比较，能发现唯一索引拦不住的大小写、空白差异
+    pub async fn find_duplicates(
+        db: &DatabaseConnection,
+        scope: &AccountScope,
+        field: Option<DuplicateField>,
+    ) -> Result<DuplicatesResponse, DbErr> {
+        Self::duplicates_in(db, Self::find_scoped(scope), field).await
+    }
+
+    // 全表（含已删除的账号）的重复报告，与唯一索引的范围一致。
+    // 只读取基础列，可以在迁移之前运行（duplicates 子命令）
+    pub async fn find_all_duplicates(
+        db: &DatabaseConnection,
+        field: Option<DuplicateField>,
+    ) -> Result<DuplicatesResponse, DbErr> {
+        Self::duplicates_in(db, account::Entity::find(), field).await
+    }
+
+    async fn duplicates_in(
+        db: &DatabaseConnection,
+        query: Select<account::Entity>,
+        field: Option<DuplicateField>,
+    ) -> Result<DuplicatesResponse, DbErr> {
+        let accounts = query
+            .select_only()
+            .columns([
+                account::Column::Id,
+                account::Column::Account,
+                account::Column::ClientId,
+                account::Column::CompanyName,
+                account::Column::UserName,
+                account::Column::IsEnable,
+                account::Column::CreateTime,
+            ])
+            .order_by_asc(account::Column::Id)
+            .into_model::<DuplicateAccount>()
+            .all(db)
+            .await?;
+
+        let fields = match field {
+            Some(field) => vec![field],
+            None => DuplicateField::ALL.to_vec(),
+        };
+
+        let mut groups = Vec::new();
+        for field in fields {
+            // BTreeMap 保证输出顺序稳定
+            let mut by_value: BTreeMap<String, Vec<&DuplicateAccount>> = BTreeMap::new();
+            for model in &accounts {
+                let value = match field {
+                    DuplicateField::ClientId => model.client_id.trim().to_string(),
+                    DuplicateField::Account => Self::normalize_name(&model.account),
+                    DuplicateField::CompanyName => Self::normalize_name(&model.company_name),
+                };
+                if !value.is_empty() {
+                    by_value.entry(value).or_default().push(model);
+                }
+            }
+
+            groups.extend(by_value.into_iter().filter(|(_, models)| models.len() > 1).map(
+                |(value, models)| DuplicateGroup {
+                    field,
+                    value,
+                    accounts: models.into_iter().cloned().collect(),
+                },
+            ));
+        }
+
+        Ok(DuplicatesResponse {
+            total_groups: groups.len(),
+            groups,
+        })
+    }
+
+    fn normalize_name(name: &str) -> String {
+        name.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    }
+
+    fn is_unique_violation(err: &DbErr) -> bool {
+        matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_)))
+    }
+
+    // api_key 在 before 之前过期（含已过期）的账号，按过期时间升序
+    pub async fn get_expiring_accounts(
+        db: &DatabaseConnection,
+        scope: &AccountScope,
+        before: u32,
+        page: u64,
+        page_size: u64,
+    ) -> Result<AccountsListResponse, sea_orm::DbErr> {
+        let offset = (page - 1) * page_size;
+
+        let query = Self::find_scoped(scope)
+            .filter(account::Column::KeyExpiresAt.is_not_null())
+            .filter(account::Column::KeyExpiresAt.lte(before));
+
+        let total = query.clone().count(db).await?;
+
+        let accounts = query
+            .order_by_asc(account::Column::KeyExpiresAt)
+            .order_by_asc(account::Column::Id)
+            .offset(offset)
+            .limit(page_size)
+            .all(db)
+            .await?;
+
+        Ok(AccountsListResponse {
+            total,
+            data: accounts.into_iter().map(AccountResponse::from).collect(),
+            page,
+            page_size,
+            total_pages: total.div_ceil(page_size),
+        })
+    }
+
+    // 在同一事务中修改账号并写入审计日志。
+    // 乐观锁：UPDATE 的 WHERE 条件带上期望的 update_time，版本不一致时不做修改
+    async fn update_with_audit<F>(
+        db: &DatabaseConnection,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+        action: &str,
+        apply: F,
+    ) -> Result<UpdateResult, sea_orm::DbErr>
+    where
+        F: FnOnce(&mut account::ActiveModel),
+    {
+        Self::update_matching(db, Self::find_scoped(scope), ctx, id, expected_version, action, apply).await
+    }
+
+    // 同 update_with_audit，修改前用 query 查找账号
+    async fn update_matching<F>(
+        db: &DatabaseConnection,
+        query: Select<account::Entity>,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+        action: &str,
+        apply: F,
+    ) -> Result<UpdateResult, sea_orm::DbErr>
+    where
+        F: FnOnce(&mut account::ActiveModel),
+    {
+        let txn = db.begin().await?;
+
+        let Some(before) = query
+            .filter(account::Column::Id.eq(id))
+            .one(&txn)
+            .await?
+        else {
+            return Ok(UpdateResult::NotFound);
+        };
+
+        let Some(after) =
+            Self::apply_versioned_update(&txn, ctx, &before, expected_version, action, apply).await?
+        else {
+            return Ok(UpdateResult::VersionMismatch(before));
+        };
+
+        txn.commit().await?;
+        Ok(UpdateResult::Updated(after))
+    }
+
+    // 在给定事务上执行一次带版本校验的修改并写入审计日志，版本不一致时返回 None
+    pub async fn apply_versioned_update<C, F>(
+        conn: &C,
+        ctx: &AuditContext,
+        before: &account::Model,
+        expected_version: u32,
+        action: &str,
+        apply: F,
+    ) -> Result<Option<account::Model>, sea_orm::DbErr>
+    where
+        C: ConnectionTrait,
+        F: FnOnce(&mut account::ActiveModel),
+    {
+        // 秒级时间戳可能在同一秒内重复，保证新版本号严格递增
+        let new_version = (chrono::Utc::now().timestamp() as u32).max(before.update_time + 1);
+
+        let mut changes = account::ActiveModel::default();
+        apply(&mut changes);
+        changes.update_time = Set(new_version);
+
+        let result = account::Entity::update_many()
+            .set(changes)
+            .filter(account::Column::Id.eq(before.id))
+            .filter(account::Column::UpdateTime.eq(expected_version))
+            .exec(conn)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Ok(None);
+        }
+
+        let after = account::Entity::find_by_id(before.id)
+            .one(conn)
+            .await?
+            .ok_or_else(|| sea_orm::DbErr::RecordNotFound(format!("account {}", before.id)))?;
+
+        AuditService::record(conn, ctx, before.id, action, Some(before), Some(&after)).await?;
+
+        Ok(Some(after))
+    }
+
+    // 批量修改账号。所有条目在同一事务中执行，每个账号使用独立的 savepoint：
+    // best_effort 模式下失败的账号单独回滚；all_or_nothing 模式下任一失败整体回滚；
+    // dry_run 完整执行后回滚，返回的结果即为预览
+    pub async fn bulk_update(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        request: &BulkRequest,
+    ) -> Result<BulkResponse, sea_orm::DbErr> {
+        let txn = db.begin().await?;
+        let mut results = Vec::new();
+
+        for (index, operation) in request.operations.iter().enumerate() {
+            let targets = match Self::resolve_bulk_targets(&txn, scope, operation).await? {
+                Ok(targets) => targets,
+                Err(message) => {
+                    results.push(BulkItemResult {
+                        operation: index,
+                        id: None,
+                        status: BulkItemStatus::Failed,
+                        error: Some(message),
+                    });
+                    continue;
+                }
+            };
+
+            for (id, target) in targets {
+                let Some(before) = target else {
+                    results.push(BulkItemResult {
+                        operation: index,
+                        id: Some(id),
+                        status: BulkItemStatus::NotFound,
+                        error: None,
+                    });
+                    continue;
+                };
+
+                let (status, error) = Self::apply_bulk_action(&txn, ctx, &before, &operation.action).await?;
+                results.push(BulkItemResult {
+                    operation: index,
+                    id: Some(id),
+                    status,
+                    error,
+                });
+            }
+        }
+
+        let count = |status: BulkItemStatus| results.iter().filter(|r| r.status == status).count() as u64;
+        let updated = count(BulkItemStatus::Updated);
+        // all_or_nothing 模式下找不到的账号同样视为失败
+        let failed = match request.mode {
+            BulkMode::AllOrNothing => count(BulkItemStatus::Failed) + count(BulkItemStatus::NotFound),
+            BulkMode::BestEffort => count(BulkItemStatus::Failed),
+        };
+        let matched = results.iter().filter(|r| r.id.is_some() && r.status != BulkItemStatus::NotFound).count() as u64;
+
+        let committed = !request.dry_run && (request.mode == BulkMode::BestEffort || failed == 0);
+        if committed {
+            txn.commit().await?;
+            if updated > 0 {
+                cache.invalidate_all();
+            }
+        } else {
+            txn.rollback().await?;
+        }
+
+        Ok(BulkResponse {
+            mode: request.mode,
+            dry_run: request.dry_run,
+            committed,
+            matched,
+            updated,
+            failed,
+            results,
+        })
+    }
+
+    // 解析操作的目标账号：按 ids 时保留请求顺序并标记不可见的 id；
+    // 按 filter 时匹配数超过上限直接拒绝该操作
+    async fn resolve_bulk_targets<C: ConnectionTrait>(
+        conn: &C,
+        scope: &AccountScope,
+        operation: &BulkOperation,
+    ) -> Result<Result<Vec<(u32, Option<account::Model>)>, String>, sea_orm::DbErr> {
+        if let Some(ids) = &operation.ids {
+            // 去重并保留首次出现的顺序
+            let mut seen = HashSet::new();
+            let ids: Vec<u32> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+
+            let models = Self::find_scoped(scope)
+                .filter(account::Column::Id.is_in(ids.iter().copied()))
+                .all(conn)
+                .await?;
+
+            let targets = ids
+                .into_iter()
+                .map(|id| (id, models.iter().find(|m| m.id == id).cloned()))
+                .collect();
+            return Ok(Ok(targets));
+        }
+
+        let mut query = Self::find_scoped(scope);
+        if let Some(filter) = &operation.filter {
+            if let Some(is_enable) = filter.is_enable {
+                query = query.filter(account::Column::IsEnable.eq(is_enable));
+            }
+            if let Some(company_name) = &filter.company_name {
+                query = query.filter(account::Column::CompanyName.eq(company_name.as_str()));
+            }
+            if let Some(currency_code) = &filter.currency_code {
+                query = query.filter(account::Column::CurrencyCode.eq(currency_code.as_str()));
+            }
+            if let Some(user_name) = &filter.user_name {
+                query = query.filter(account::Column::UserName.eq(user_name.as_str()));
+            }
+        }
+
+        let models = query
+            .order_by_asc(account::Column::Id)
+            .limit(MAX_BULK_ITEMS + 1)
+            .all(conn)
+            .await?;
+
+        if models.len() as u64 > MAX_BULK_ITEMS {
+            return Ok(Err(format!("filter matches more than {} accounts", MAX_BULK_ITEMS)));
+        }
+
+        Ok(Ok(models.into_iter().map(|m| (m.id, Some(m))).collect()))
+    }
+
+    // 对单个账号执行批量操作，使用 savepoint 保证失败时只回滚该账号
+    async fn apply_bulk_action(
+        txn: &DatabaseTransaction,
+        ctx: &AuditContext,
+        before: &account::Model,
+        action: &BulkAction,
+    ) -> Result<(BulkItemStatus, Option<String>), sea_orm::DbErr> {
+        let unchanged = match action {
+            BulkAction::Enable => before.is_enable == 1,
+            BulkAction::Disable => before.is_enable == 0,
+            BulkAction::SetOwner { user_name } => before.user_name == user_name.trim(),
+            BulkAction::SetCurrency { currency_code } => before.currency_code == *currency_code,
+        };
+        if unchanged {
+            return Ok((BulkItemStatus::Unchanged, None));
+        }
+
+        let audit_action = match action {
+            BulkAction::Enable => actions::ENABLE,
+            BulkAction::Disable => actions::DISABLE,
+            BulkAction::SetOwner { .. } => actions::REASSIGN_OWNER,
+            BulkAction::SetCurrency { .. } => actions::UPDATE,
+        };
+
+        let savepoint = txn.begin().await?;
+        let result = Self::apply_versioned_update(&savepoint, ctx, before, before.update_time, audit_action, |active| {
+            match action {
+                BulkAction::Enable => active.is_enable = Set(1),
+                BulkAction::Disable => active.is_enable = Set(0),
+                BulkAction::SetOwner { user_name } => active.user_name = Set(user_name.trim().to_string()),
+                BulkAction::SetCurrency { currency_code } => active.currency_code = Set(currency_code.clone()),
+            }
+        })
+        .await;
+
+        match result {
+            Ok(Some(_)) => {
+                savepoint.commit().await?;
+                Ok((BulkItemStatus::Updated, None))
+            }
+            Ok(None) => {
+                savepoint.rollback().await?;
+                Ok((BulkItemStatus::Failed, Some("account was modified concurrently".to_string())))
+            }
+            Err(e) => {
+                tracing::warn!(account_id = before.id, "Bulk account update failed: {}", e);
+                savepoint.rollback().await?;
+                Ok((BulkItemStatus::Failed, Some(Self::row_error(&e))))
+            }
+        }
+    }
+
+    // 导入账号：按 client_id 新建或（upsert 时）更新。与批量操作相同，每行使用独立的
+    // savepoint，失败的行单独回滚；dry_run 完整执行后回滚
+    pub async fn import_accounts(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        rows: Vec<ParsedRow>,
+        params: &ImportParams,
+    ) -> Result<ImportResponse, sea_orm::DbErr> {
+        let txn = db.begin().await?;
+        let mut first_seen: HashMap<String, usize> = HashMap::new();
+        let mut results = Vec::with_capacity(rows.len());
+
+        for ParsedRow { line, row } in rows {
+            let row = match row {
+                Ok(row) => row,
+                Err(message) => {
+                    results.push(ImportRowResult {
+                        line,
+                        client_id: None,
+                        id: None,
+                        status: ImportRowStatus::Failed,
+                        message: Some(message),
+                    });
+                    continue;
+                }
+            };
+
+            let client_id = row.client_id.clone();
+            let outcome = match client_id.as_deref().map(|id| first_seen.get(id)) {
+                Some(Some(first)) => Err(format!("duplicate client_id, first seen on line {}", first)),
+                _ => Self::import_row(&txn, scope, ctx, row, params.upsert).await?,
+            };
+            if let Some(client_id) = &client_id {
+                first_seen.entry(client_id.clone()).or_insert(line);
+            }
+
+            let (status, id, message) = match outcome {
+                Ok((status, id, message)) => (status, id, message),
+                Err(message) => (ImportRowStatus::Failed, None, Some(message)),
+            };
+            results.push(ImportRowResult {
+                line,
+                client_id,
+                id,
+                status,
+                message,
+            });
+        }
+
+        let count = |status: ImportRowStatus| results.iter().filter(|r| r.status == status).count() as u64;
+        let created = count(ImportRowStatus::Created);
+        let updated = count(ImportRowStatus::Updated);
+
+        let committed = !params.dry_run;
+        if committed {
+            txn.commit().await?;
+            if created + updated > 0 {
+                cache.invalidate_all();
+            }
+        } else {
+            txn.rollback().await?;
+        }
+
+        Ok(ImportResponse {
+            dry_run: params.dry_run,
+            upsert: params.upsert,
+            committed,
+            created,
+            updated,
+            skipped: count(ImportRowStatus::Skipped),
+            failed: count(ImportRowStatus::Failed),
+            rows: results,
+        })
+    }
+
+    // 导入单行。外层 Err 为数据库错误，内层 Err 为该行的失败原因
+    async fn import_row(
+        txn: &DatabaseTransaction,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        mut row: ImportRow,
+        upsert: bool,
+    ) -> Result<Result<ImportOutcome, String>, sea_orm::DbErr> {
+        let client_id = row.client_id.clone().unwrap_or_default();
+
+        // 不按可见范围过滤，client_id 在全表内唯一
+        let existing = account::Entity::find()
+            .filter(account::Column::ClientId.eq(client_id.as_str()))
+            .one(txn)
+            .await?;
+
+        if let Some(before) = existing {
+            if before.deleted_at.is_some() && scope.allows_owner(&before.user_name) {
+                return Ok(Err("client_id belongs to a deleted account, restore it first".to_string()));
+            }
+            if let Err(message) = row.validate(false) {
+                return Ok(Err(message));
+            }
+            if !scope.allows_owner(&before.user_name) {
+                return Ok(Err("client_id is already used by another account".to_string()));
+            }
+            if !upsert {
+                return Ok(Ok((ImportRowStatus::Skipped, Some(before.id), Some("already exists".to_string()))));
+            }
+            if row.user_name.as_deref().is_some_and(|owner| !scope.allows_owner(owner)) {
+                return Ok(Err("user_name is outside the caller's scope".to_string()));
+            }
+
+            let changed = row.account.as_ref().is_some_and(|v| *v != before.account)
+                || row.currency_code.as_ref().is_some_and(|v| *v != before.currency_code)
+                || row.company_name.as_ref().is_some_and(|v| *v != before.company_name)
+                || row.data.as_ref().is_some_and(|v| *v != before.data)
+                || row.is_enable.is_some_and(|v| v != before.is_enable)
+                || row.user_name.as_ref().is_some_and(|v| *v != before.user_name);
+            // 新 api_key 不直接覆盖当前 key，而是作为待启用的 key 走轮换流程
+            let new_key = row
+                .api_key
+                .take()
+                .filter(|v| *v != before.api_key && Some(v) != before.pending_api_key.as_ref());
+            if !changed && new_key.is_none() {
+                return Ok(Ok((ImportRowStatus::Skipped, Some(before.id), Some("no changes".to_string()))));
+            }
+            if let Some(message) = Self::find_conflict(txn, None, row.account.as_deref(), Some(before.id)).await? {
+                return Ok(Err(message));
+            }
+
+            let savepoint = txn.begin().await?;
+            let result = Self::import_update(&savepoint, ctx, &before, row, changed, new_key.clone()).await;
+
+            return Ok(match result {
+                Ok(Some(after)) => {
+                    savepoint.commit().await?;
+                    let message = new_key
+                        .map(|_| "api_key staged as pending key, verify and promote it to take effect".to_string());
+                    Ok((ImportRowStatus::Updated, Some(after.id), message))
+                }
+                Ok(None) => {
+                    savepoint.rollback().await?;
+                    Err("account was modified concurrently".to_string())
+                }
+                Err(e) => {
+                    tracing::warn!(client_id = %client_id, "Account import update failed: {}", e);
+                    savepoint.rollback().await?;
+                    Err(Self::row_error(&e))
+                }
+            });
+        }
+
+        if let Err(message) = row.validate(true) {
+            return Ok(Err(message));
+        }
+        // 受限调用方只能有一个可选负责人时自动填充，否则必须显式指定
+        if row.user_name.is_none() {
+            if let AccountScope::Owners(owners) = scope {
+                if let [owner] = owners.as_slice() {
+                    row.user_name = Some(owner.clone());
+                } else {
+                    return Ok(Err("user_name is required".to_string()));
+                }
+            }
+        }
+        if row.user_name.as_deref().is_some_and(|owner| !scope.allows_owner(owner)) {
+            return Ok(Err("user_name is outside the caller's scope".to_string()));
+        }
+        if let Some(message) = Self::find_conflict(txn, None, row.account.as_deref(), None).await? {
+            return Ok(Err(message));
+        }
+
+        let now = chrono::Utc::now().timestamp() as u32;
+        let model = account::ActiveModel {
+            id: NotSet,
+            account: Set(row.account.unwrap_or_default()),
+            client_id: Set(client_id.clone()),
+            api_key: Set(row.api_key.unwrap_or_default()),
+            currency_code: Set(row.currency_code.unwrap_or_default()),
+            company_name: Set(row.company_name.unwrap_or_default()),
+            data: Set(row.data.unwrap_or_default()),
+            is_enable: Set(row.is_enable.unwrap_or(1)),
+            user_name: Set(row.user_name.unwrap_or_default()),
+            create_time: Set(now),
+            update_time: Set(now),
+            verify_status: Set(verify_status::UNKNOWN.to_string()),
+            last_verified_at: Set(None),
+            last_verify_error: Set(None),
+            key_expires_at: Set(None),
+            key_rotated_at: Set(None),
+            pending_api_key: Set(None),
+            pending_key_expires_at: Set(None),
+            pending_verify_status: Set(None),
+            pending_verified_at: Set(None),
+            pending_verify_error: Set(None),
+            previous_api_key: Set(None),
+            previous_key_expires_at: Set(None),
+            previous_key_valid_until: Set(None),
+            deleted_at: Set(None),
+        };
+
+        let savepoint = txn.begin().await?;
+        let result = async {
+            let created = account::Entity::insert(model).exec_with_returning(&savepoint).await?;
+            AuditService::record(&savepoint, ctx, created.id, actions::CREATE, None, Some(&created)).await?;
+            Ok::<_, sea_orm::DbErr>(created)
+        }
+        .await;
+
+        Ok(match result {
+            Ok(created) => {
+                savepoint.commit().await?;
+                Ok((ImportRowStatus::Created, Some(created.id), None))
+            }
+            Err(e) => {
+                tracing::warn!(client_id = %client_id, "Account import insert failed: {}", e);
+                savepoint.rollback().await?;
+                Err(Self::row_error(&e))
+            }
+        })
+    }
+
+    // 导入时更新已有账号：先修改普通字段，再把新 api_key 暂存为待启用的 key，
+    // 两步都写审计日志；任一步版本不一致时返回 None
+    async fn import_update(
+        savepoint: &DatabaseTransaction,
+        ctx: &AuditContext,
+        before: &account::Model,
+        row: ImportRow,
+        changed: bool,
+        new_key: Option<String>,
+    ) -> Result<Option<account::Model>, sea_orm::DbErr> {
+        let mut current = before.clone();
+
+        if changed {
+            let updated = Self::apply_versioned_update(savepoint, ctx, &current, current.update_time, actions::UPDATE, |active| {
+                if let Some(v) = row.account {
+                    active.account = Set(v);
+                }
+                if let Some(v) = row.currency_code {
+                    active.currency_code = Set(v);
+                }
+                if let Some(v) = row.company_name {
+                    active.company_name = Set(v);
+                }
+                if let Some(v) = row.data {
+                    active.data = Set(v);
+                }
+                if let Some(v) = row.is_enable {
+                    active.is_enable = Set(v);
+                }
+                if let Some(v) = row.user_name {
+                    active.user_name = Set(v);
+                }
+            })
+            .await?;
+            let Some(updated) = updated else { return Ok(None) };
+            current = updated;
+        }
+
+        if let Some(api_key) = new_key {
+            return Self::apply_versioned_update(savepoint, ctx, &current, current.update_time, actions::STAGE_KEY, |active| {
+                RotationService::set_pending(active, api_key, None);
+            })
+            .await;
+        }
+        Ok(Some(current))
+    }
+
+    // 单行失败时返回给调用方的说明；原始 DbErr 可能带有 SQL 和表结构，只写日志
+    fn row_error(err: &DbErr) -> String {
+        if Self::is_unique_violation(err) {
+            "client_id or account name is already used by another account".to_string()
+        } else {
+            "failed to write account, see server logs".to_string()
+        }
+    }
+
+    // 写入成功后清空缓存，保证后续读取不会拿到旧数据
+    fn invalidate_if_updated(cache: &AccountCache, result: UpdateResult) -> UpdateResult {
+        if matches!(result, UpdateResult::Updated(_)) {
+            cache.invalidate_all();
+        }
+        result
+    }
 }
\ No newline at end of file
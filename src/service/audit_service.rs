@@ -0,0 +1,204 @@
+use sea_orm::sea_query::SelectStatement;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::{NotSet, Set}, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use serde_json::{json, Map, Value};
+use crate::auth::Principal;
+use crate::entities::{account, account_audit};
+use crate::middleware::request_id::current_request_id;
+use crate::models::account::AccountResponse;
+use crate::models::audit::{AuditListResponse, AuditLogResponse, AuditQueryParams};
+
+// 审计动作
+pub mod actions {
+    pub const CREATE: &str = "create";
+    pub const ENABLE: &str = "enable";
+    pub const DISABLE: &str = "disable";
+    pub const REASSIGN_OWNER: &str = "reassign_owner";
+    pub const UPDATE: &str = "update";
+    pub const CREDENTIALS_VIEWED: &str = "credentials_viewed";
+    // 导出时包含明文 api_key，每个导出的账号一条
+    pub const CREDENTIALS_EXPORTED: &str = "credentials_exported";
+    // api_key 轮换
+    pub const STAGE_KEY: &str = "stage_key";
+    pub const VERIFY_PENDING_KEY: &str = "verify_pending_key";
+    pub const CANCEL_PENDING_KEY: &str = "cancel_pending_key";
+    pub const PROMOTE_KEY: &str = "promote_key";
+    pub const ROLLBACK_KEY: &str = "rollback_key";
+    // 软删除与保留期清理
+    pub const DELETE: &str = "delete";
+    pub const RESTORE: &str = "restore";
+    pub const PURGE: &str = "purge";
+}
+
+// 写审计日志所需的上下文：操作人 + 当前请求 ID
+#[derive(Clone, Debug)]
+pub struct AuditContext {
+    pub actor: String,
+    pub request_id: Option<String>,
+}
+
+impl AuditContext {
+    pub fn from_principal(principal: &Principal) -> Self {
+        Self {
+            actor: principal.subject.clone(),
+            request_id: current_request_id(),
+        }
+    }
+}
+
+pub struct AuditService;
+
+impl AuditService {
+    // 写入一条审计记录，传入事务连接即可与业务修改一起提交
+    pub async fn record<C: ConnectionTrait>(
+        conn: &C,
+        ctx: &AuditContext,
+        account_id: u32,
+        action: &str,
+        before: Option<&account::Model>,
+        after: Option<&account::Model>,
+    ) -> Result<(), sea_orm::DbErr> {
+        let entry = account_audit::ActiveModel {
+            id: NotSet,
+            account_id: Set(account_id),
+            actor: Set(ctx.actor.clone()),
+            action: Set(action.to_string()),
+            changes: Set(Self::diff(before, after).to_string()),
+            request_id: Set(ctx.request_id.clone()),
+            create_time: Set(chrono::Utc::now().timestamp() as u32),
+        };
+
+        entry.insert(conn).await?;
+        Ok(())
+    }
+
+    // 为多个账号写入同一动作的审计记录（不含字段变化），一条 INSERT 完成
+    pub async fn record_many<C: ConnectionTrait>(
+        conn: &C,
+        ctx: &AuditContext,
+        account_ids: &[u32],
+        action: &str,
+    ) -> Result<(), sea_orm::DbErr> {
+        if account_ids.is_empty() {
+            return Ok(());
+        }
+
+        let now = chrono::Utc::now().timestamp() as u32;
+        let changes = Self::diff(None, None).to_string();
+        let entries = account_ids.iter().map(|&account_id| account_audit::ActiveModel {
+            id: NotSet,
+            account_id: Set(account_id),
+            actor: Set(ctx.actor.clone()),
+            action: Set(action.to_string()),
+            changes: Set(changes.clone()),
+            request_id: Set(ctx.request_id.clone()),
+            create_time: Set(now),
+        });
+
+        account_audit::Entity::insert_many(entries).exec(conn).await?;
+        Ok(())
+    }
+
+    // 对比修改前后的字段，只保留有变化的字段。
+    // 凭证不写入审计日志，只用 api_key_changed / pending_api_key_changed 记录是否变化
+    pub fn diff(before: Option<&account::Model>, after: Option<&account::Model>) -> Value {
+        let before_fields = before.map(Self::to_fields).unwrap_or_default();
+        let after_fields = after.map(Self::to_fields).unwrap_or_default();
+
+        let mut changes = Map::new();
+        for key in before_fields.keys().chain(after_fields.keys()) {
+            let old = before_fields.get(key).unwrap_or(&Value::Null);
+            let new = after_fields.get(key).unwrap_or(&Value::Null);
+            if old != new && !changes.contains_key(key) {
+                changes.insert(key.clone(), json!({ "before": old, "after": new }));
+            }
+        }
+
+        let secrets = [
+            (
+                "api_key_changed",
+                before.map(|m| m.api_key.as_str()),
+                after.map(|m| m.api_key.as_str()),
+            ),
+            (
+                "pending_api_key_changed",
+                before.and_then(|m| m.pending_api_key.as_deref()),
+                after.and_then(|m| m.pending_api_key.as_deref()),
+            ),
+        ];
+        for (key, old, new) in secrets {
+            if old != new {
+                changes.insert(key.to_string(), Value::Bool(true));
+            }
+        }
+
+        Value::Object(changes)
+    }
+
+    fn to_fields(model: &account::Model) -> Map<String, Value> {
+        match serde_json::to_value(AccountResponse::from(model.clone())) {
+            Ok(Value::Object(mut fields)) => {
+                fields.remove("api_key");
+                fields
+            }
+            _ => Map::new(),
+        }
+    }
+
+    // 按条件分页查询审计日志（按时间倒序）；visible_ids 为调用方可见账号的 id 子查询，
+    // None 表示不按账号范围过滤
+    pub async fn search(
+        db: &DatabaseConnection,
+        params: &AuditQueryParams,
+        visible_ids: Option<SelectStatement>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<AuditListResponse, sea_orm::DbErr> {
+        let offset = (page - 1) * page_size;
+
+        let mut query = account_audit::Entity::find();
+        if let Some(visible_ids) = visible_ids {
+            query = query.filter(account_audit::Column::AccountId.in_subquery(visible_ids));
+        }
+        if let Some(account_id) = params.account_id {
+            query = query.filter(account_audit::Column::AccountId.eq(account_id));
+        }
+        if let Some(actor) = &params.actor {
+            query = query.filter(account_audit::Column::Actor.eq(actor.as_str()));
+        }
+        if let Some(action) = &params.action {
+            query = query.filter(account_audit::Column::Action.eq(action.as_str()));
+        }
+        if let Some(request_id) = &params.request_id {
+            query = query.filter(account_audit::Column::RequestId.eq(request_id.as_str()));
+        }
+        if let Some(from) = params.from {
+            query = query.filter(account_audit::Column::CreateTime.gte(from));
+        }
+        if let Some(to) = params.to {
+            query = query.filter(account_audit::Column::CreateTime.lte(to));
+        }
+
+        let total = query.clone().count(db).await?;
+
+        let entries = query
+            .order_by_desc(account_audit::Column::CreateTime)
+            .order_by_desc(account_audit::Column::Id)
+            .offset(offset)
+            .limit(page_size)
+            .all(db)
+            .await?;
+
+        let total_pages = total.div_ceil(page_size);
+
+        Ok(AuditListResponse {
+            total,
+            data: entries.into_iter().map(AuditLogResponse::from).collect(),
+            page,
+            page_size,
+            total_pages,
+        })
+    }
+}
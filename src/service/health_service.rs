@@ -0,0 +1,43 @@
+use std::time::{Duration, Instant};
+use sea_orm::DatabaseConnection;
+use crate::models::health::{DependencyHealth, PoolStats};
+
+pub struct HealthService;
+
+impl HealthService {
+    // Ping 数据库并记录耗时，超时视为不健康
+    pub async fn check_database(db: &DatabaseConnection, timeout: Duration) -> DependencyHealth {
+        let start = Instant::now();
+        let result = tokio::time::timeout(timeout, db.ping()).await;
+        let latency_ms = start.elapsed().as_millis() as u64;
+
+        let error = match result {
+            Ok(Ok(())) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some(format!("ping timed out after {}ms", timeout.as_millis())),
+        };
+
+        DependencyHealth {
+            name: "database".to_string(),
+            healthy: error.is_none(),
+            latency_ms,
+            error,
+        }
+    }
+
+    pub fn pool_stats(db: &DatabaseConnection) -> PoolStats {
+        let pool = db.get_mysql_connection_pool();
+        let size = pool.size();
+        let idle = pool.num_idle() as u32;
+        let max = pool.options().get_max_connections();
+        let in_use = size.saturating_sub(idle);
+
+        PoolStats {
+            size,
+            idle,
+            in_use,
+            max,
+            saturation: if max == 0 { 0.0 } else { in_use as f64 / max as f64 },
+        }
+    }
+}
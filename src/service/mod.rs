@@ -1,2 +1,10 @@
 pub mod fibonacci_service;
-pub mod account_service;
\ No newline at end of file
+pub mod account_service;
+pub mod audit_service;
+pub mod health_service;
+pub mod verification_service;
+pub mod posting_service;
+pub mod product_service;
+pub mod rotation_service;
+pub mod sync_service;
+pub mod retention_service;
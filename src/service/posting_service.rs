@@ -0,0 +1,236 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveValue::{NotSet, Set}, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect,
+};
+use crate::config::SyncSettings;
+use crate::entities::{account, posting, sync_cursor};
+use crate::models::posting::{PostingQueryParams, PostingResponse, PostingsListResponse};
+use crate::ozon::{
+    models::{FbsPosting, FbsPostingListFilter, FbsPostingListRequest},
+    OzonClient,
+};
+use crate::service::sync_service::{resources, SyncError, SyncReport, SyncService};
+use crate::shutdown::Shutdown;
+
+// 单次请求的时间窗口，窗口完成后推进水位，中断后从该水位继续
+const POSTING_WINDOW_SECS: u32 = 7 * 24 * 3600;
+
+pub struct PostingService;
+
+impl PostingService {
+    // 按 in_process_at 时间窗口增量拉取账号的 FBS 货件。游标保存已同步到的时间戳，
+    // 每次从水位往前重叠 posting_overlap 秒，按 posting_number 幂等写入
+    pub async fn sync_account(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        account: &account::Model,
+        settings: &SyncSettings,
+    ) -> Result<SyncReport, SyncError> {
+        let cursor = SyncService::acquire(db, account.id, resources::POSTINGS, settings.lease).await?;
+        Self::sync_with_cursor(db, ozon, account, settings, &cursor).await
+    }
+
+    // 手动触发：抢占游标后在后台执行同步，立即返回游标状态。
+    // 同步不依赖请求的生命周期，客户端断开或超时不会让游标停留在 running
+    pub async fn start_sync(
+        db: DatabaseConnection,
+        ozon: OzonClient,
+        account: account::Model,
+        settings: SyncSettings,
+        shutdown: &Shutdown,
+    ) -> Result<sync_cursor::Model, SyncError> {
+        let cursor = SyncService::acquire(&db, account.id, resources::POSTINGS, settings.lease).await?;
+
+        let task_cursor = cursor.clone();
+        shutdown.spawn(async move {
+            let result = Self::sync_with_cursor(&db, &ozon, &account, &settings, &task_cursor).await;
+            SyncService::log_result(account.id, resources::POSTINGS, result);
+        });
+
+        Ok(cursor)
+    }
+
+    async fn sync_with_cursor(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        account: &account::Model,
+        settings: &SyncSettings,
+        cursor: &sync_cursor::Model,
+    ) -> Result<SyncReport, SyncError> {
+        match Self::run(db, ozon, account, settings, cursor).await {
+            Ok(report) => Ok(report),
+            Err(e) => {
+                if let Err(db_err) = SyncService::fail(db, cursor.id, &e).await {
+                    tracing::error!(account_id = account.id, "Failed to record sync failure: {}", db_err);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn run(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        account: &account::Model,
+        settings: &SyncSettings,
+        cursor: &sync_cursor::Model,
+    ) -> Result<SyncReport, SyncError> {
+        let now = Utc::now().timestamp() as u32;
+        let mut since = match cursor.cursor.parse::<u32>() {
+            Ok(watermark) => watermark.saturating_sub(settings.posting_overlap as u32),
+            // 首次同步
+            Err(_) => now.saturating_sub(settings.posting_lookback_days.saturating_mul(24 * 3600)),
+        };
+        let mut synced = 0u32;
+
+        while since < now {
+            let to = since.saturating_add(POSTING_WINDOW_SECS).min(now);
+            let mut offset = 0;
+
+            loop {
+                let request = FbsPostingListRequest {
+                    dir: "ASC".to_string(),
+                    filter: FbsPostingListFilter {
+                        since: Self::format_time(since),
+                        to: Self::format_time(to),
+                        status: None,
+                    },
+                    limit: settings.page_size,
+                    offset,
+                };
+                let page = ozon.fbs_posting_list(account, &request).await?.result;
+                if page.postings.is_empty() {
+                    break;
+                }
+
+                synced += page.postings.len() as u32;
+                Self::upsert(db, account.id, &page.postings).await?;
+                SyncService::heartbeat(db, cursor.id).await?;
+
+                if !page.has_next {
+                    break;
+                }
+                offset += settings.page_size;
+            }
+
+            sync_cursor::Entity::update_many()
+                .col_expr(sync_cursor::Column::Cursor, Expr::value(to.to_string()))
+                .col_expr(sync_cursor::Column::ItemsSynced, Expr::value(synced))
+                .filter(sync_cursor::Column::Id.eq(cursor.id))
+                .exec(db)
+                .await?;
+            since = to;
+        }
+
+        SyncService::complete(db, cursor.id, &now.to_string(), synced).await?;
+
+        Ok(SyncReport {
+            synced: synced.into(),
+            removed: 0,
+        })
+    }
+
+    async fn upsert(
+        db: &DatabaseConnection,
+        account_id: u32,
+        postings: &[FbsPosting],
+    ) -> Result<(), sea_orm::DbErr> {
+        let now = Utc::now().timestamp() as u32;
+        let rows = postings.iter().map(|p| Self::to_active_model(account_id, p, now));
+
+        posting::Entity::insert_many(rows)
+            .on_conflict(
+                OnConflict::columns([posting::Column::AccountId, posting::Column::PostingNumber])
+                    .update_columns([
+                        posting::Column::OrderId,
+                        posting::Column::OrderNumber,
+                        posting::Column::Status,
+                        posting::Column::InProcessAt,
+                        posting::Column::ShipmentDate,
+                        posting::Column::Products,
+                        posting::Column::SyncedAt,
+                        posting::Column::UpdateTime,
+                    ])
+                    .to_owned(),
+            )
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+
+    fn to_active_model(account_id: u32, posting: &FbsPosting, now: u32) -> posting::ActiveModel {
+        posting::ActiveModel {
+            id: NotSet,
+            account_id: Set(account_id),
+            posting_number: Set(posting.posting_number.clone()),
+            order_id: Set(posting.order_id),
+            order_number: Set(posting.order_number.clone()),
+            status: Set(posting.status.clone()),
+            in_process_at: Set(Self::parse_time(posting.in_process_at.as_deref())),
+            shipment_date: Set(Self::parse_time(posting.shipment_date.as_deref())),
+            products: Set(serde_json::to_string(&posting.products).unwrap_or_else(|_| "[]".to_string())),
+            synced_at: Set(now),
+            create_time: Set(now),
+            update_time: Set(now),
+        }
+    }
+
+    fn format_time(ts: u32) -> String {
+        DateTime::<Utc>::from_timestamp(ts.into(), 0)
+            .unwrap_or_default()
+            .to_rfc3339_opts(SecondsFormat::Secs, true)
+    }
+
+    fn parse_time(value: Option<&str>) -> Option<u32> {
+        value
+            .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+            .map(|t| t.timestamp() as u32)
+    }
+
+    // 查询账号在本地同步的货件，按开始处理时间倒序（调用方需先确认账号可见）
+    pub async fn list_postings(
+        db: &DatabaseConnection,
+        account_id: u32,
+        params: &PostingQueryParams,
+        page: u64,
+        page_size: u64,
+    ) -> Result<PostingsListResponse, sea_orm::DbErr> {
+        let offset = (page - 1) * page_size;
+
+        let mut query = posting::Entity::find().filter(posting::Column::AccountId.eq(account_id));
+        if let Some(status) = params.status.as_deref().filter(|s| !s.is_empty()) {
+            query = query.filter(posting::Column::Status.eq(status));
+        }
+        if let Some(from) = params.from {
+            query = query.filter(posting::Column::InProcessAt.gte(from));
+        }
+        if let Some(to) = params.to {
+            query = query.filter(posting::Column::InProcessAt.lte(to));
+        }
+
+        let total = query.clone().count(db).await?;
+
+        let postings = query
+            .order_by_desc(posting::Column::InProcessAt)
+            .order_by_desc(posting::Column::Id)
+            .offset(offset)
+            .limit(page_size)
+            .all(db)
+            .await?;
+
+        let sync = SyncService::find(db, account_id, resources::POSTINGS)
+            .await?
+            .map(Into::into);
+
+        Ok(PostingsListResponse {
+            total,
+            data: postings.into_iter().map(PostingResponse::from).collect(),
+            page,
+            page_size,
+            total_pages: total.div_ceil(page_size),
+            sync,
+        })
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveValue::{NotSet, Set}, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use crate::config::SyncSettings;
+use crate::entities::{account, product, sync_cursor};
+use crate::models::product::{ProductQueryParams, ProductResponse, ProductsListResponse};
+use crate::ozon::{
+    models::{ProductInfo, ProductInfoListRequest, ProductListItem, ProductListRequest},
+    OzonClient,
+};
+use crate::service::sync_service::{resources, SyncError, SyncReport, SyncService};
+use crate::shutdown::Shutdown;
+
+pub struct ProductService;
+
+impl ProductService {
+    // 分页拉取账号的全部商品并写入本地表。每页与游标在同一事务中提交，
+    // 中断后从上次的 last_id 继续；整轮完成后删除本轮未再出现的商品
+    pub async fn sync_account(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        account: &account::Model,
+        settings: &SyncSettings,
+    ) -> Result<SyncReport, SyncError> {
+        let cursor = SyncService::acquire(db, account.id, resources::PRODUCTS, settings.lease).await?;
+        Self::sync_with_cursor(db, ozon, account, settings, &cursor).await
+    }
+
+    // 手动触发：抢占游标后在后台执行同步，立即返回游标状态。
+    // 同步不依赖请求的生命周期，客户端断开或超时不会让游标停留在 running
+    pub async fn start_sync(
+        db: DatabaseConnection,
+        ozon: OzonClient,
+        account: account::Model,
+        settings: SyncSettings,
+        shutdown: &Shutdown,
+    ) -> Result<sync_cursor::Model, SyncError> {
+        let cursor = SyncService::acquire(&db, account.id, resources::PRODUCTS, settings.lease).await?;
+
+        let task_cursor = cursor.clone();
+        shutdown.spawn(async move {
+            let result = Self::sync_with_cursor(&db, &ozon, &account, &settings, &task_cursor).await;
+            SyncService::log_result(account.id, resources::PRODUCTS, result);
+        });
+
+        Ok(cursor)
+    }
+
+    async fn sync_with_cursor(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        account: &account::Model,
+        settings: &SyncSettings,
+        cursor: &sync_cursor::Model,
+    ) -> Result<SyncReport, SyncError> {
+        match Self::run_pass(db, ozon, account, settings, cursor).await {
+            Ok(report) => Ok(report),
+            Err(e) => {
+                if let Err(db_err) = SyncService::fail(db, cursor.id, &e).await {
+                    tracing::error!(account_id = account.id, "Failed to record sync failure: {}", db_err);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn run_pass(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        account: &account::Model,
+        settings: &SyncSettings,
+        cursor: &sync_cursor::Model,
+    ) -> Result<SyncReport, SyncError> {
+        let pass_started_at = cursor.pass_started_at.unwrap_or(0);
+        let mut last_id = cursor.cursor.clone();
+        let mut synced = cursor.items_synced;
+
+        loop {
+            let request = ProductListRequest {
+                last_id: last_id.clone(),
+                limit: settings.page_size,
+                ..Default::default()
+            };
+            let page = ozon.product_list(account, &request).await?.result;
+            if page.items.is_empty() {
+                break;
+            }
+
+            let info_request = ProductInfoListRequest {
+                product_id: page.items.iter().map(|item| item.product_id).collect(),
+                ..Default::default()
+            };
+            let details: HashMap<i64, ProductInfo> = ozon
+                .product_info_list(account, &info_request)
+                .await?
+                .items
+                .into_iter()
+                .map(|info| (info.id, info))
+                .collect();
+
+            let now = chrono::Utc::now().timestamp() as u32;
+            let count = page.items.len() as u32;
+            let rows: Vec<product::ActiveModel> = page
+                .items
+                .iter()
+                .map(|item| Self::to_active_model(account.id, item, details.get(&item.product_id), now))
+                .collect();
+
+            let txn = db.begin().await?;
+            product::Entity::insert_many(rows)
+                .on_conflict(
+                    OnConflict::columns([product::Column::AccountId, product::Column::ProductId])
+                        .update_columns([
+                            product::Column::OfferId,
+                            product::Column::Name,
+                            product::Column::Barcodes,
+                            product::Column::CurrencyCode,
+                            product::Column::Price,
+                            product::Column::OldPrice,
+                            product::Column::Archived,
+                            product::Column::HasFboStocks,
+                            product::Column::HasFbsStocks,
+                            product::Column::OzonUpdatedAt,
+                            product::Column::SyncedAt,
+                            product::Column::UpdateTime,
+                        ])
+                        .to_owned(),
+                )
+                .exec(&txn)
+                .await?;
+
+            synced += count;
+            sync_cursor::Entity::update_many()
+                .col_expr(sync_cursor::Column::Cursor, Expr::value(page.last_id.clone()))
+                .col_expr(sync_cursor::Column::ItemsSynced, Expr::value(synced))
+                .filter(sync_cursor::Column::Id.eq(cursor.id))
+                .exec(&txn)
+                .await?;
+            SyncService::heartbeat(&txn, cursor.id).await?;
+            txn.commit().await?;
+
+            if page.last_id.is_empty() || count < settings.page_size {
+                break;
+            }
+            last_id = page.last_id;
+        }
+
+        // 本轮完整同步中没有出现的商品已在 Ozon 侧删除
+        let txn = db.begin().await?;
+        let removed = product::Entity::delete_many()
+            .filter(product::Column::AccountId.eq(account.id))
+            .filter(product::Column::SyncedAt.lt(pass_started_at))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        SyncService::complete(&txn, cursor.id, "", synced).await?;
+        txn.commit().await?;
+
+        Ok(SyncReport {
+            synced: synced.into(),
+            removed,
+        })
+    }
+
+    fn to_active_model(
+        account_id: u32,
+        item: &ProductListItem,
+        info: Option<&ProductInfo>,
+        now: u32,
+    ) -> product::ActiveModel {
+        let (name, barcodes, currency_code, price, old_price, ozon_updated_at) = match info {
+            Some(info) => (
+                info.name.clone(),
+                info.barcodes.join(","),
+                info.currency_code.clone(),
+                info.price.clone(),
+                info.old_price.clone(),
+                info.updated_at.clone(),
+            ),
+            None => Default::default(),
+        };
+
+        product::ActiveModel {
+            id: NotSet,
+            account_id: Set(account_id),
+            product_id: Set(item.product_id),
+            offer_id: Set(item.offer_id.clone()),
+            name: Set(name),
+            barcodes: Set(barcodes),
+            currency_code: Set(currency_code),
+            price: Set(price),
+            old_price: Set(old_price),
+            archived: Set(u8::from(item.archived || info.is_some_and(|i| i.is_archived))),
+            has_fbo_stocks: Set(u8::from(item.has_fbo_stocks)),
+            has_fbs_stocks: Set(u8::from(item.has_fbs_stocks)),
+            ozon_updated_at: Set(ozon_updated_at),
+            synced_at: Set(now),
+            create_time: Set(now),
+            update_time: Set(now),
+        }
+    }
+
+    // 查询账号在本地同步的商品（调用方需先确认账号可见）
+    pub async fn list_products(
+        db: &DatabaseConnection,
+        account_id: u32,
+        params: &ProductQueryParams,
+        page: u64,
+        page_size: u64,
+    ) -> Result<ProductsListResponse, sea_orm::DbErr> {
+        let offset = (page - 1) * page_size;
+
+        let mut query = product::Entity::find().filter(product::Column::AccountId.eq(account_id));
+        if let Some(offer_id) = &params.offer_id {
+            query = query.filter(product::Column::OfferId.eq(offer_id.as_str()));
+        }
+        if let Some(archived) = params.archived {
+            query = query.filter(product::Column::Archived.eq(u8::from(archived)));
+        }
+        if let Some(name) = params.name.as_deref().filter(|n| !n.is_empty()) {
+            query = query.filter(product::Column::Name.contains(name));
+        }
+
+        let total = query.clone().count(db).await?;
+
+        let products = query
+            .order_by_asc(product::Column::ProductId)
+            .offset(offset)
+            .limit(page_size)
+            .all(db)
+            .await?;
+
+        let sync = SyncService::find(db, account_id, resources::PRODUCTS)
+            .await?
+            .map(Into::into);
+
+        Ok(ProductsListResponse {
+            total,
+            data: products.into_iter().map(ProductResponse::from).collect(),
+            page,
+            page_size,
+            total_pages: total.div_ceil(page_size),
+            sync,
+        })
+    }
+}
@@ -0,0 +1,104 @@
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+use crate::cache::AccountCache;
+use crate::config::AccountRetentionSettings;
+use crate::entities::{account, posting, product, sync_cursor};
+use crate::service::audit_service::{actions, AuditContext, AuditService};
+use crate::shutdown::Shutdown;
+
+// 保留期清理在审计日志中的操作人
+const RETENTION_ACTOR: &str = "system:retention";
+
+// 已删除（归档）账号的保留期清理：超过保留期后彻底删除账号及其本地同步数据，
+// 审计日志保留，并追加一条 purge 记录
+pub struct RetentionService;
+
+impl RetentionService {
+    // 彻底删除 deleted_at 不晚于 cutoff 的账号，返回删除的账号数。
+    // 每个账号单独一个事务，删除时带版本校验，避免与并发的恢复操作冲突
+    pub async fn purge_deleted_accounts(db: &DatabaseConnection, cutoff: u32) -> Result<u64, sea_orm::DbErr> {
+        let accounts = account::Entity::find()
+            .filter(account::Column::DeletedAt.is_not_null())
+            .filter(account::Column::DeletedAt.lte(cutoff))
+            .order_by_asc(account::Column::Id)
+            .all(db)
+            .await?;
+
+        let ctx = AuditContext {
+            actor: RETENTION_ACTOR.to_string(),
+            request_id: None,
+        };
+
+        let mut purged = 0;
+        for model in &accounts {
+            let txn = db.begin().await?;
+
+            let result = account::Entity::delete_many()
+                .filter(account::Column::Id.eq(model.id))
+                .filter(account::Column::UpdateTime.eq(model.update_time))
+                .filter(account::Column::DeletedAt.is_not_null())
+                .exec(&txn)
+                .await?;
+            if result.rows_affected == 0 {
+                txn.rollback().await?;
+                continue;
+            }
+
+            product::Entity::delete_many()
+                .filter(product::Column::AccountId.eq(model.id))
+                .exec(&txn)
+                .await?;
+            posting::Entity::delete_many()
+                .filter(posting::Column::AccountId.eq(model.id))
+                .exec(&txn)
+                .await?;
+            sync_cursor::Entity::delete_many()
+                .filter(sync_cursor::Column::AccountId.eq(model.id))
+                .exec(&txn)
+                .await?;
+
+            AuditService::record(&txn, &ctx, model.id, actions::PURGE, Some(model), None).await?;
+            txn.commit().await?;
+            purged += 1;
+        }
+
+        Ok(purged)
+    }
+
+    pub fn spawn_retention_purge(
+        settings: &AccountRetentionSettings,
+        db: DatabaseConnection,
+        cache: AccountCache,
+        shutdown: &Shutdown,
+    ) {
+        if !settings.enabled || settings.interval == 0 {
+            return;
+        }
+
+        let retention = u64::from(settings.retention_days) * 86400;
+        let interval = std::time::Duration::from_secs(settings.interval);
+        let task_shutdown = shutdown.clone();
+
+        shutdown.spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = task_shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+
+                let now = chrono::Utc::now().timestamp() as u64;
+                let cutoff = now.saturating_sub(retention) as u32;
+
+                match Self::purge_deleted_accounts(&db, cutoff).await {
+                    Ok(0) => {}
+                    Ok(purged) => {
+                        cache.invalidate_all();
+                        tracing::info!(purged, "Deleted accounts past retention purged");
+                    }
+                    Err(e) => tracing::error!("Failed to purge deleted accounts: {}", e),
+                }
+            }
+        });
+    }
+}
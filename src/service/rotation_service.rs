@@ -0,0 +1,291 @@
+use sea_orm::{
+    sea_query::Expr, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    TransactionTrait,
+};
+use thiserror::Error;
+use crate::auth::AccountScope;
+use crate::cache::AccountCache;
+use crate::entities::account;
+use crate::error::AppError;
+use crate::models::account::StageKeyRequest;
+use crate::ozon::OzonClient;
+use crate::service::account_service::{AccountService, UpdateResult};
+use crate::service::audit_service::{actions, AuditContext};
+use crate::service::verification_service::{status as verify_status, VerificationService};
+use crate::shutdown::Shutdown;
+
+// 清理过期旧 key 的间隔
+const CLEANUP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(3600);
+
+#[derive(Debug, Error)]
+pub enum RotationError {
+    #[error("database error: {0}")]
+    Db(#[from] sea_orm::DbErr),
+
+    // 账号当前的轮换状态不允许该操作
+    #[error("{0}")]
+    InvalidState(String),
+}
+
+impl From<RotationError> for AppError {
+    fn from(err: RotationError) -> Self {
+        match err {
+            RotationError::Db(e) => e.into(),
+            RotationError::InvalidState(msg) => AppError::Conflict(msg),
+        }
+    }
+}
+
+// api_key 轮换：暂存新 key -> 用 Ozon 校验 -> 启用（旧 key 保留一段宽限期）。
+// 每一步都是带版本校验的单条 UPDATE，并写入审计日志作为轮换历史
+pub struct RotationService;
+
+impl RotationService {
+    pub async fn stage_key(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+        request: &StageKeyRequest,
+    ) -> Result<UpdateResult, RotationError> {
+        let before = match Self::load(db, scope, id, expected_version).await? {
+            Ok(model) => model,
+            Err(result) => return Ok(result),
+        };
+        if before.api_key == request.api_key {
+            return Err(RotationError::InvalidState(
+                "pending api_key must differ from the current api_key".to_string(),
+            ));
+        }
+
+        Self::apply(db, cache, ctx, &before, expected_version, actions::STAGE_KEY, |active| {
+            Self::set_pending(active, request.api_key.clone(), request.key_expires_at);
+        })
+        .await
+    }
+
+    pub async fn cancel_pending(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+    ) -> Result<UpdateResult, RotationError> {
+        let before = match Self::load(db, scope, id, expected_version).await? {
+            Ok(model) => model,
+            Err(result) => return Ok(result),
+        };
+        Self::require_pending(&before)?;
+
+        Self::apply(db, cache, ctx, &before, expected_version, actions::CANCEL_PENDING_KEY, |active| {
+            Self::clear_pending(active);
+        })
+        .await
+    }
+
+    // 用暂存的新 key 调用 Ozon 并记录结果。校验期间新 key 被替换或取消时返回版本冲突
+    pub async fn verify_pending(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+    ) -> Result<UpdateResult, RotationError> {
+        let Some(before) = AccountService::get_account_by_id(db, scope, id).await? else {
+            return Ok(UpdateResult::NotFound);
+        };
+        let pending = Self::require_pending(&before)?;
+
+        let candidate = account::Model {
+            api_key: pending.to_string(),
+            ..before.clone()
+        };
+        let (status, error) = VerificationService::probe(ozon, &candidate).await;
+        let now = chrono::Utc::now().timestamp() as u32;
+
+        Self::apply(db, cache, ctx, &before, before.update_time, actions::VERIFY_PENDING_KEY, |active| {
+            active.pending_verify_status = Set(Some(status.to_string()));
+            active.pending_verified_at = Set(Some(now));
+            active.pending_verify_error = Set(error);
+        })
+        .await
+    }
+
+    // 启用已校验通过的新 key，旧 key 保留 grace_period 秒
+    pub async fn promote(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+        grace_period: u64,
+    ) -> Result<UpdateResult, RotationError> {
+        let before = match Self::load(db, scope, id, expected_version).await? {
+            Ok(model) => model,
+            Err(result) => return Ok(result),
+        };
+        let pending = Self::require_pending(&before)?.to_string();
+        if before.pending_verify_status.as_deref() != Some(verify_status::OK) {
+            return Err(RotationError::InvalidState(
+                "pending api_key must be verified successfully before promotion".to_string(),
+            ));
+        }
+
+        let now = chrono::Utc::now().timestamp() as u32;
+        let keep_previous = grace_period > 0;
+
+        Self::apply(db, cache, ctx, &before, expected_version, actions::PROMOTE_KEY, |active| {
+            active.api_key = Set(pending);
+            active.key_expires_at = Set(before.pending_key_expires_at);
+            active.key_rotated_at = Set(Some(now));
+            active.verify_status = Set(verify_status::OK.to_string());
+            active.last_verified_at = Set(before.pending_verified_at);
+            active.last_verify_error = Set(None);
+            active.previous_api_key = Set(keep_previous.then(|| before.api_key.clone()));
+            active.previous_key_expires_at = Set(before.key_expires_at.filter(|_| keep_previous));
+            active.previous_key_valid_until =
+                Set(keep_previous.then(|| now.saturating_add(grace_period as u32)));
+            Self::clear_pending(active);
+        })
+        .await
+    }
+
+    // 宽限期内恢复旧 key
+    pub async fn rollback(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        ctx: &AuditContext,
+        id: u32,
+        expected_version: u32,
+    ) -> Result<UpdateResult, RotationError> {
+        let before = match Self::load(db, scope, id, expected_version).await? {
+            Ok(model) => model,
+            Err(result) => return Ok(result),
+        };
+
+        let now = chrono::Utc::now().timestamp() as u32;
+        let previous = before
+            .previous_api_key
+            .clone()
+            .filter(|_| before.previous_key_valid_until.is_some_and(|until| until > now))
+            .ok_or_else(|| {
+                RotationError::InvalidState("no previous api_key within the grace period".to_string())
+            })?;
+
+        Self::apply(db, cache, ctx, &before, expected_version, actions::ROLLBACK_KEY, |active| {
+            active.api_key = Set(previous);
+            active.key_expires_at = Set(before.previous_key_expires_at);
+            active.key_rotated_at = Set(Some(now));
+            active.verify_status = Set(verify_status::UNKNOWN.to_string());
+            active.last_verify_error = Set(None);
+            active.previous_api_key = Set(None);
+            active.previous_key_expires_at = Set(None);
+            active.previous_key_valid_until = Set(None);
+        })
+        .await
+    }
+
+    // 账号不存在或版本不一致时直接返回对应的 UpdateResult
+    async fn load(
+        db: &DatabaseConnection,
+        scope: &AccountScope,
+        id: u32,
+        expected_version: u32,
+    ) -> Result<Result<account::Model, UpdateResult>, sea_orm::DbErr> {
+        Ok(match AccountService::get_account_by_id(db, scope, id).await? {
+            None => Err(UpdateResult::NotFound),
+            Some(model) if model.update_time != expected_version => Err(UpdateResult::VersionMismatch(model)),
+            Some(model) => Ok(model),
+        })
+    }
+
+    fn require_pending(model: &account::Model) -> Result<&str, RotationError> {
+        model
+            .pending_api_key
+            .as_deref()
+            .ok_or_else(|| RotationError::InvalidState("account has no pending api_key".to_string()))
+    }
+
+    // 暂存新 key，等待校验后启用（导入时修改 api_key 也走这里）
+    pub fn set_pending(active: &mut account::ActiveModel, api_key: String, key_expires_at: Option<u32>) {
+        active.pending_api_key = Set(Some(api_key));
+        active.pending_key_expires_at = Set(key_expires_at);
+        active.pending_verify_status = Set(Some(verify_status::UNKNOWN.to_string()));
+        active.pending_verified_at = Set(None);
+        active.pending_verify_error = Set(None);
+    }
+
+    fn clear_pending(active: &mut account::ActiveModel) {
+        active.pending_api_key = Set(None);
+        active.pending_key_expires_at = Set(None);
+        active.pending_verify_status = Set(None);
+        active.pending_verified_at = Set(None);
+        active.pending_verify_error = Set(None);
+    }
+
+    async fn apply<F>(
+        db: &DatabaseConnection,
+        cache: &AccountCache,
+        ctx: &AuditContext,
+        before: &account::Model,
+        expected_version: u32,
+        action: &str,
+        apply: F,
+    ) -> Result<UpdateResult, RotationError>
+    where
+        F: FnOnce(&mut account::ActiveModel),
+    {
+        let txn = db.begin().await?;
+        let Some(after) =
+            AccountService::apply_versioned_update(&txn, ctx, before, expected_version, action, apply).await?
+        else {
+            return Ok(UpdateResult::VersionMismatch(before.clone()));
+        };
+        txn.commit().await?;
+
+        cache.invalidate_all();
+        Ok(UpdateResult::Updated(after))
+    }
+
+    // 删除宽限期已过的旧 key，不修改 update_time。响应在读取时已按宽限期隐藏该字段，
+    // 集合版本也已计入到期时间，清理前后列表内容不变
+    pub async fn purge_expired_previous_keys(db: &DatabaseConnection) -> Result<u64, sea_orm::DbErr> {
+        let now = chrono::Utc::now().timestamp() as u32;
+        let result = account::Entity::update_many()
+            .col_expr(account::Column::PreviousApiKey, Expr::value(Option::<String>::None))
+            .col_expr(account::Column::PreviousKeyExpiresAt, Expr::value(Option::<u32>::None))
+            .col_expr(account::Column::PreviousKeyValidUntil, Expr::value(Option::<u32>::None))
+            .filter(account::Column::PreviousKeyValidUntil.lte(now))
+            .exec(db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    pub fn spawn_previous_key_cleanup(db: DatabaseConnection, cache: AccountCache, shutdown: &Shutdown) {
+        let task_shutdown = shutdown.clone();
+
+        shutdown.spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = task_shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(CLEANUP_INTERVAL) => {}
+                }
+
+                match Self::purge_expired_previous_keys(&db).await {
+                    Ok(0) => {}
+                    Ok(purged) => {
+                        cache.invalidate_all();
+                        tracing::info!(purged, "Expired previous api keys purged");
+                    }
+                    Err(e) => tracing::error!("Failed to purge expired previous api keys: {}", e),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,263 @@
+use sea_orm::{
+    sea_query::{Expr, OnConflict},
+    ActiveValue::{NotSet, Set}, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    QueryFilter, QueryOrder,
+};
+use thiserror::Error;
+use crate::config::SyncSettings;
+use crate::entities::{account, sync_cursor};
+use crate::error::AppError;
+use crate::ozon::{OzonClient, OzonError};
+use crate::service::posting_service::PostingService;
+use crate::service::product_service::ProductService;
+use crate::service::verification_service::status as verify_status;
+use crate::shutdown::Shutdown;
+
+// 同步的数据类型，对应 sync_cursor.resource
+pub mod resources {
+    pub const PRODUCTS: &str = "products";
+    pub const POSTINGS: &str = "postings";
+}
+
+// sync_cursor.status 的取值
+pub mod status {
+    pub const IDLE: &str = "idle";
+    pub const RUNNING: &str = "running";
+    pub const FAILED: &str = "failed";
+}
+
+const MAX_ERROR_LEN: usize = 512;
+
+#[derive(Debug, Error)]
+pub enum SyncError {
+    #[error("{0}")]
+    Ozon(#[from] OzonError),
+
+    #[error("database error: {0}")]
+    Db(#[from] sea_orm::DbErr),
+
+    // 同一账号的同类同步已在运行（可能在其他实例上）
+    #[error("{0} sync is already running")]
+    AlreadyRunning(&'static str),
+}
+
+impl From<SyncError> for AppError {
+    fn from(err: SyncError) -> Self {
+        match err {
+            SyncError::Ozon(e) => e.into(),
+            SyncError::Db(e) => e.into(),
+            SyncError::AlreadyRunning(_) => AppError::Conflict(err.to_string()),
+        }
+    }
+}
+
+// 一次同步的结果
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub synced: u64,
+    pub removed: u64,
+}
+
+pub struct SyncService;
+
+impl SyncService {
+    // 抢占账号的同步游标并标记为运行中。运行中的同步每提交一页刷新一次 last_started_at，
+    // 超过 lease 秒没有刷新的 running 游标视为上次运行异常中断，可以接管
+    pub async fn acquire(
+        db: &DatabaseConnection,
+        account_id: u32,
+        resource: &'static str,
+        lease: u64,
+    ) -> Result<sync_cursor::Model, SyncError> {
+        let now = chrono::Utc::now().timestamp() as u32;
+
+        // 首次同步时创建游标，已存在则不做修改
+        sync_cursor::Entity::insert(sync_cursor::ActiveModel {
+            id: NotSet,
+            account_id: Set(account_id),
+            resource: Set(resource.to_string()),
+            cursor: Set(String::new()),
+            status: Set(status::IDLE.to_string()),
+            pass_started_at: Set(None),
+            last_started_at: Set(None),
+            last_finished_at: Set(None),
+            last_success_at: Set(None),
+            last_error: Set(None),
+            items_synced: Set(0),
+        })
+        .on_conflict(
+            OnConflict::columns([sync_cursor::Column::AccountId, sync_cursor::Column::Resource])
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(db)
+        .await?;
+
+        // 条件更新保证多实例下只有一个能拿到游标
+        let stale_before = now.saturating_sub(lease as u32);
+        let result = sync_cursor::Entity::update_many()
+            .col_expr(sync_cursor::Column::Status, Expr::value(status::RUNNING))
+            .col_expr(sync_cursor::Column::LastStartedAt, Expr::value(now))
+            .filter(sync_cursor::Column::AccountId.eq(account_id))
+            .filter(sync_cursor::Column::Resource.eq(resource))
+            .filter(
+                Condition::any()
+                    .add(sync_cursor::Column::Status.ne(status::RUNNING))
+                    .add(sync_cursor::Column::LastStartedAt.lt(stale_before))
+                    .add(sync_cursor::Column::LastStartedAt.is_null()),
+            )
+            .exec(db)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(SyncError::AlreadyRunning(resource));
+        }
+
+        let mut cursor = Self::find(db, account_id, resource)
+            .await?
+            .ok_or_else(|| sea_orm::DbErr::RecordNotFound(format!("{} sync cursor", resource)))?;
+
+        // 游标为空表示开始新一轮完整同步
+        if cursor.cursor.is_empty() {
+            sync_cursor::Entity::update_many()
+                .col_expr(sync_cursor::Column::PassStartedAt, Expr::value(now))
+                .col_expr(sync_cursor::Column::ItemsSynced, Expr::value(0))
+                .filter(sync_cursor::Column::Id.eq(cursor.id))
+                .exec(db)
+                .await?;
+            cursor.pass_started_at = Some(now);
+            cursor.items_synced = 0;
+        }
+
+        Ok(cursor)
+    }
+
+    pub async fn find(
+        db: &DatabaseConnection,
+        account_id: u32,
+        resource: &str,
+    ) -> Result<Option<sync_cursor::Model>, sea_orm::DbErr> {
+        sync_cursor::Entity::find()
+            .filter(sync_cursor::Column::AccountId.eq(account_id))
+            .filter(sync_cursor::Column::Resource.eq(resource))
+            .one(db)
+            .await
+    }
+
+    // 本轮同步成功结束：写入下一轮的起点（空表示从头开始），记录完成时间
+    pub async fn complete<C: sea_orm::ConnectionTrait>(
+        conn: &C,
+        cursor_id: u32,
+        next_cursor: &str,
+        items_synced: u32,
+    ) -> Result<(), sea_orm::DbErr> {
+        let now = chrono::Utc::now().timestamp() as u32;
+        sync_cursor::Entity::update_many()
+            .col_expr(sync_cursor::Column::Cursor, Expr::value(next_cursor))
+            .col_expr(sync_cursor::Column::Status, Expr::value(status::IDLE))
+            .col_expr(sync_cursor::Column::LastFinishedAt, Expr::value(now))
+            .col_expr(sync_cursor::Column::LastSuccessAt, Expr::value(now))
+            .col_expr(sync_cursor::Column::LastError, Expr::value(Option::<String>::None))
+            .col_expr(sync_cursor::Column::ItemsSynced, Expr::value(items_synced))
+            .filter(sync_cursor::Column::Id.eq(cursor_id))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    // 运行中的心跳：刷新 last_started_at，表示该同步仍在进行
+    pub async fn heartbeat<C: sea_orm::ConnectionTrait>(conn: &C, cursor_id: u32) -> Result<(), sea_orm::DbErr> {
+        let now = chrono::Utc::now().timestamp() as u32;
+        sync_cursor::Entity::update_many()
+            .col_expr(sync_cursor::Column::LastStartedAt, Expr::value(now))
+            .filter(sync_cursor::Column::Id.eq(cursor_id))
+            .filter(sync_cursor::Column::Status.eq(status::RUNNING))
+            .exec(conn)
+            .await?;
+        Ok(())
+    }
+
+    // 同步失败：保留游标以便下次从断点继续
+    pub async fn fail(db: &DatabaseConnection, cursor_id: u32, error: &SyncError) -> Result<(), sea_orm::DbErr> {
+        let now = chrono::Utc::now().timestamp() as u32;
+        let message: String = error.to_string().chars().take(MAX_ERROR_LEN).collect();
+        sync_cursor::Entity::update_many()
+            .col_expr(sync_cursor::Column::Status, Expr::value(status::FAILED))
+            .col_expr(sync_cursor::Column::LastFinishedAt, Expr::value(now))
+            .col_expr(sync_cursor::Column::LastError, Expr::value(message))
+            .filter(sync_cursor::Column::Id.eq(cursor_id))
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+
+    // 需要同步的账号：未删除、启用且凭证未被 Ozon 拒绝
+    async fn syncable_accounts(db: &DatabaseConnection) -> Result<Vec<account::Model>, sea_orm::DbErr> {
+        account::Entity::find()
+            .filter(account::Column::IsEnable.eq(1))
+            .filter(account::Column::DeletedAt.is_null())
+            .filter(account::Column::VerifyStatus.ne(verify_status::INVALID))
+            .order_by_asc(account::Column::Id)
+            .all(db)
+            .await
+    }
+
+    // 定期同步所有账号的数据；多实例部署时由游标保证同一账号不会被并发同步
+    pub fn spawn_periodic_sync(
+        settings: &SyncSettings,
+        db: DatabaseConnection,
+        ozon: OzonClient,
+        shutdown: &Shutdown,
+    ) {
+        if !settings.enabled || settings.interval == 0 {
+            return;
+        }
+
+        let settings = settings.clone();
+        let interval = std::time::Duration::from_secs(settings.interval);
+        let task_shutdown = shutdown.clone();
+
+        shutdown.spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = task_shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+
+                let accounts = match Self::syncable_accounts(&db).await {
+                    Ok(accounts) => accounts,
+                    Err(e) => {
+                        tracing::error!("Failed to load accounts for sync: {}", e);
+                        continue;
+                    }
+                };
+
+                for account in &accounts {
+                    if task_shutdown.is_draining() {
+                        break;
+                    }
+                    let result = ProductService::sync_account(&db, &ozon, account, &settings).await;
+                    Self::log_result(account.id, resources::PRODUCTS, result);
+                    let result = PostingService::sync_account(&db, &ozon, account, &settings).await;
+                    Self::log_result(account.id, resources::POSTINGS, result);
+                }
+            }
+        });
+    }
+
+    pub fn log_result(account_id: u32, resource: &str, result: Result<SyncReport, SyncError>) {
+        match result {
+            Ok(report) => tracing::info!(
+                account_id,
+                resource,
+                synced = report.synced,
+                removed = report.removed,
+                "Sync finished"
+            ),
+            // 其他实例正在同步该账号
+            Err(SyncError::AlreadyRunning(_)) => {}
+            Err(e) => tracing::warn!(account_id, resource, "Sync failed: {}", e),
+        }
+    }
+}
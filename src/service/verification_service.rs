@@ -0,0 +1,152 @@
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+};
+use crate::auth::AccountScope;
+use crate::cache::AccountCache;
+use crate::config::CredentialCheckSettings;
+use crate::entities::account;
+use crate::ozon::{models::ProductListRequest, OzonClient, OzonError};
+use crate::service::account_service::AccountService;
+use crate::shutdown::Shutdown;
+
+// 账号 verify_status 的取值
+pub mod status {
+    pub const UNKNOWN: &str = "unknown";
+    pub const OK: &str = "ok";
+    // Ozon 拒绝了凭证，需要人工更新 api_key
+    pub const INVALID: &str = "invalid";
+    // 网络、限流等临时错误，无法判断凭证是否有效
+    pub const ERROR: &str = "error";
+}
+
+// 错误信息入库前截断，与 last_verify_error 列长度一致
+const MAX_ERROR_LEN: usize = 512;
+
+pub struct VerificationService;
+
+impl VerificationService {
+    // 用账号凭证调用一次开销最小的 Seller API 接口，并记录结果；账号不存在时返回 None
+    pub async fn verify_account(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        cache: &AccountCache,
+        scope: &AccountScope,
+        id: u32,
+    ) -> Result<Option<account::Model>, sea_orm::DbErr> {
+        let Some(model) = AccountService::get_account_by_id(db, scope, id).await? else {
+            return Ok(None);
+        };
+
+        Self::check_and_record(db, ozon, &model).await?;
+        cache.invalidate_all();
+
+        account::Entity::find_by_id(id).one(db).await
+    }
+
+    // 依次校验所有未删除的启用账号，返回 (成功数, 失败数)；收到退出信号时提前结束
+    pub async fn verify_enabled_accounts(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        cache: &AccountCache,
+        shutdown: &Shutdown,
+    ) -> Result<(u64, u64), sea_orm::DbErr> {
+        let accounts = account::Entity::find()
+            .filter(account::Column::IsEnable.eq(1))
+            .filter(account::Column::DeletedAt.is_null())
+            .order_by_asc(account::Column::Id)
+            .all(db)
+            .await?;
+
+        let (mut ok, mut failed) = (0, 0);
+        for model in &accounts {
+            if shutdown.is_draining() {
+                break;
+            }
+            if Self::check_and_record(db, ozon, model).await? == status::OK {
+                ok += 1;
+            } else {
+                failed += 1;
+            }
+        }
+
+        if ok + failed > 0 {
+            cache.invalidate_all();
+        }
+        Ok((ok, failed))
+    }
+
+    async fn check_and_record(
+        db: &DatabaseConnection,
+        ozon: &OzonClient,
+        model: &account::Model,
+    ) -> Result<&'static str, sea_orm::DbErr> {
+        let (verify_status, error) = Self::probe(ozon, model).await;
+
+        // 校验结果不属于账号版本，不修改 update_time，避免后台任务导致 If-Match 冲突
+        account::Entity::update_many()
+            .col_expr(account::Column::VerifyStatus, Expr::value(verify_status))
+            .col_expr(
+                account::Column::LastVerifiedAt,
+                Expr::value(chrono::Utc::now().timestamp() as u32),
+            )
+            .col_expr(account::Column::LastVerifyError, Expr::value(error))
+            .filter(account::Column::Id.eq(model.id))
+            .exec(db)
+            .await?;
+
+        Ok(verify_status)
+    }
+
+    // 用账号上的 client_id / api_key 调用一次 Seller API，返回 (校验状态, 错误信息)
+    pub async fn probe(ozon: &OzonClient, model: &account::Model) -> (&'static str, Option<String>) {
+        let request = ProductListRequest {
+            limit: 1,
+            ..Default::default()
+        };
+
+        match ozon.product_list(model, &request).await {
+            Ok(_) => (status::OK, None),
+            Err(e) => {
+                tracing::warn!(account_id = model.id, "Account credential check failed: {}", e);
+                let verify_status = if e.is_auth_error() { status::INVALID } else { status::ERROR };
+                (verify_status, Some(Self::error_message(&e)))
+            }
+        }
+    }
+
+    fn error_message(err: &OzonError) -> String {
+        err.to_string().chars().take(MAX_ERROR_LEN).collect()
+    }
+
+    // 定期校验启用账号的凭证；多实例部署时建议只在一个实例上开启
+    pub fn spawn_periodic_check(
+        settings: &CredentialCheckSettings,
+        db: DatabaseConnection,
+        ozon: OzonClient,
+        cache: AccountCache,
+        shutdown: &Shutdown,
+    ) {
+        if !settings.enabled || settings.interval == 0 {
+            return;
+        }
+
+        let interval = std::time::Duration::from_secs(settings.interval);
+        let task_shutdown = shutdown.clone();
+
+        shutdown.spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = task_shutdown.cancelled() => break,
+                    _ = tokio::time::sleep(interval) => {}
+                }
+
+                match Self::verify_enabled_accounts(&db, &ozon, &cache, &task_shutdown).await {
+                    Ok((ok, failed)) => {
+                        tracing::info!(ok, failed, "Periodic credential check finished");
+                    }
+                    Err(e) => tracing::error!("Periodic credential check failed: {}", e),
+                }
+            }
+        });
+    }
+}
@@ -2,12 +2,26 @@ use std::sync::Arc;
 use std::time::Duration;
 use axum::extract::FromRef;
 use sea_orm::DatabaseConnection;
+use crate::auth::Authenticator;
+use crate::cache::AccountCache;
+use crate::ozon::OzonClient;
 use crate::config::AppConfig;
+use crate::logging::LogFilterHandle;
+use crate::metrics::Metrics;
+use crate::migration::Migrator;
+use sea_orm_migration::MigratorTrait;
+use crate::shutdown::Shutdown;
 
 #[derive(Clone)]
 pub struct AppState {
     pub db: DatabaseConnection,
     pub config: Arc<AppConfig>,
+    pub shutdown: Shutdown,
+    pub log_filter: LogFilterHandle,
+    pub metrics: Arc<Metrics>,
+    pub auth: Arc<Authenticator>,
+    pub account_cache: AccountCache,
+    pub ozon: OzonClient,
 }
 
 // 实现 FromRef，让 Handler 可以自动提取 DatabaseConnection
@@ -24,8 +38,71 @@ impl FromRef<AppState> for Arc<AppConfig> {
     }
 }
 
+impl FromRef<AppState> for Shutdown {
+    fn from_ref(state: &AppState) -> Self {
+        state.shutdown.clone()
+    }
+}
+
+impl FromRef<AppState> for LogFilterHandle {
+    fn from_ref(state: &AppState) -> Self {
+        state.log_filter.clone()
+    }
+}
+
+impl FromRef<AppState> for OzonClient {
+    fn from_ref(state: &AppState) -> Self {
+        state.ozon.clone()
+    }
+}
+
+impl FromRef<AppState> for AccountCache {
+    fn from_ref(state: &AppState) -> Self {
+        state.account_cache.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<Metrics> {
+    fn from_ref(state: &AppState) -> Self {
+        state.metrics.clone()
+    }
+}
+
 impl AppState {
-    pub async fn new(config: AppConfig) -> Result<Self, sea_orm::DbErr> {
+    pub async fn new(
+        config: AppConfig,
+        log_filter: LogFilterHandle,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let auth = Arc::new(Authenticator::from_settings(&config.auth)?);
+
+        let mut db = Self::connect_database(&config).await?;
+
+        if config.database.auto_migrate {
+            Migrator::up(&db, None).await?;
+            tracing::info!("Database migrations applied");
+        }
+
+        // 注册查询耗时指标
+        let metrics = Arc::new(Metrics::new()?);
+        metrics.instrument_db(&mut db);
+
+        let account_cache = AccountCache::new(&config.cache, metrics.clone());
+        let ozon = OzonClient::new(&config.ozon)?;
+
+        Ok(Self {
+            db,
+            config: Arc::new(config),
+            shutdown: Shutdown::new(),
+            log_filter,
+            metrics,
+            auth,
+            account_cache,
+            ozon,
+        })
+    }
+
+    // 按配置创建数据库连接池，不执行迁移（子命令可以在迁移前单独使用）
+    pub async fn connect_database(config: &AppConfig) -> Result<DatabaseConnection, sea_orm::DbErr> {
         // 构建连接池配置
         let mut opt = sea_orm::ConnectOptions::new(config.database.get_database_url());
         opt.max_connections(config.database.max_connections)
@@ -35,13 +112,16 @@ impl AppState {
             .idle_timeout(Duration::from_secs(config.database.idle_timeout))
             .max_lifetime(Duration::from_secs(config.database.max_lifetime))
             .sqlx_logging(config.database.enable_logging);
+
+        // 慢查询日志（运行在请求 span 内，会带上 request_id）
+        if config.database.slow_query_log {
+            opt.sqlx_slow_statements_logging_settings(
+                log::LevelFilter::Warn,
+                Duration::from_millis(config.database.slow_query_threshold),
+            );
+        }
         
         // 创建数据库连接池
-        let db = sea_orm::Database::connect(opt).await?;
-        
-        Ok(Self {
-            db,
-            config: Arc::new(config),
-        })
+        sea_orm::Database::connect(opt).await
     }
-}
\ No newline at end of file
+}
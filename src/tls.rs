@@ -0,0 +1,120 @@
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use crate::config::TlsSettings;
+use crate::shutdown::Shutdown;
+
+// 加载证书并构建 axum-server 使用的 rustls 配置
+pub fn load_rustls_config(settings: &TlsSettings) -> io::Result<RustlsConfig> {
+    let server_config = build_server_config(settings)?;
+    Ok(RustlsConfig::from_config(Arc::new(server_config)))
+}
+
+// 定期检查证书文件的修改时间，变化后热加载（失败时保留旧证书）
+pub fn spawn_reload_watcher(config: RustlsConfig, settings: TlsSettings, shutdown: &Shutdown) {
+    if settings.reload_interval == 0 {
+        return;
+    }
+
+    let interval = Duration::from_secs(settings.reload_interval);
+    let task_shutdown = shutdown.clone();
+
+    shutdown.spawn(async move {
+        let mut last_modified = files_modified_at(&settings);
+
+        loop {
+            tokio::select! {
+                _ = task_shutdown.cancelled() => break,
+                _ = tokio::time::sleep(interval) => {}
+            }
+
+            let modified = files_modified_at(&settings);
+            if modified == last_modified {
+                continue;
+            }
+
+            match build_server_config(&settings) {
+                Ok(server_config) => {
+                    config.reload_from_config(Arc::new(server_config));
+                    last_modified = modified;
+                    tracing::info!("TLS certificates reloaded from {}", settings.cert_path);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to reload TLS certificates, keeping previous ones: {}", e);
+                }
+            }
+        }
+    });
+}
+
+fn build_server_config(settings: &TlsSettings) -> io::Result<ServerConfig> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let builder = ServerConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?;
+
+    // 配置了 client_ca_path 时启用双向 TLS
+    let builder = match &settings.client_ca_path {
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(ca_path)? {
+                roots.add(cert).map_err(io::Error::other)?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                .build()
+                .map_err(io::Error::other)?;
+            builder.with_client_cert_verifier(verifier)
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    let mut server_config = builder
+        .with_single_cert(load_certs(&settings.cert_path)?, load_private_key(&settings.key_path)?)
+        .map_err(io::Error::other)?;
+
+    // 通过 ALPN 协商 HTTP/2，回退到 HTTP/1.1
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(server_config)
+}
+
+fn load_certs(path: &str) -> io::Result<Vec<CertificateDer<'static>>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let certs = rustls_pemfile::certs(&mut reader).collect::<Result<Vec<_>, _>>()?;
+
+    if certs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no certificates found in {}", path),
+        ));
+    }
+
+    Ok(certs)
+}
+
+fn load_private_key(path: &str) -> io::Result<PrivateKeyDer<'static>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    rustls_pemfile::private_key(&mut reader)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no private key found in {}", path),
+        )
+    })
+}
+
+fn files_modified_at(settings: &TlsSettings) -> Vec<Option<SystemTime>> {
+    [
+        Some(settings.cert_path.as_str()),
+        Some(settings.key_path.as_str()),
+        settings.client_ca_path.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+    .collect()
+}